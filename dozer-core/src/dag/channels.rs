@@ -0,0 +1,39 @@
+use crate::dag::dag::PortHandle;
+use crate::dag::errors::ExecutionError;
+use dozer_types::types::Operation;
+
+/// Handed to a `Source` so it can push operations into the DAG without
+/// knowing anything about the channels or threads wired up downstream.
+pub trait SourceChannelForwarder {
+    fn send(&mut self, seq: u64, op: Operation, port: PortHandle) -> Result<(), ExecutionError>;
+
+    /// Enqueues a whole slice of operations in one channel transfer instead
+    /// of one `send` per row. Sequence numbers travel with each op, so the
+    /// executor's per-op sequence tracking sees the same `(seq, op, port)`
+    /// triples it would from an equivalent run of individual `send` calls.
+    /// The default just loops over `send`; implementations backed by a
+    /// batching channel should override it to avoid the per-row overhead.
+    fn send_batch(
+        &mut self,
+        ops: Vec<(u64, Operation)>,
+        port: PortHandle,
+    ) -> Result<(), ExecutionError> {
+        for (seq, op) in ops {
+            self.send(seq, op, port)?;
+        }
+        Ok(())
+    }
+}
+
+/// Handed to a `Processor` for the same reason, one port per output edge.
+pub trait ProcessorChannelForwarder {
+    fn send(&mut self, op: Operation, port: PortHandle) -> Result<(), ExecutionError>;
+
+    /// Batch counterpart of `send`; see `SourceChannelForwarder::send_batch`.
+    fn send_batch(&mut self, ops: Vec<Operation>, port: PortHandle) -> Result<(), ExecutionError> {
+        for op in ops {
+            self.send(op, port)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::dag::dag::NodeHandle;
+use crate::dag::errors::ExecutionError;
+use crate::state::StateStoreManager;
+use std::sync::Arc;
+
+/// Reserved state-store namespace checkpoints live under, so they get an
+/// environment of their own, apart from the nodes' processing state. `pub(crate)`
+/// so `DagExecutor` can pin this namespace to a durable `StateStoreOptions`
+/// independently of whatever sync mode the main processing state store uses.
+pub(crate) const CHECKPOINT_NAMESPACE: &str = "__dozer_checkpoints__";
+
+/// Durably tracks the highest acknowledged sequence number per source, so a
+/// restarted pipeline can resume from `from_seq` instead of reprocessing
+/// everything. Backed by the same state store the DAG's nodes use.
+pub struct CheckpointStore {
+    state: Arc<dyn StateStoreManager>,
+}
+
+impl CheckpointStore {
+    pub fn new(state: Arc<dyn StateStoreManager>) -> Self {
+        Self { state }
+    }
+
+    /// Persists `seq` as the highest acknowledged sequence number for
+    /// `source`. Called at each committed batch boundary.
+    pub fn checkpoint(&self, source: &NodeHandle, seq: u64) -> Result<(), ExecutionError> {
+        // `open_database` opens its own short-lived transaction internally, so
+        // it must run before `begin` below — LMDB only allows one write
+        // transaction per environment at a time, and nesting would deadlock.
+        let db = {
+            let mut env = self.state.init_state_store(CHECKPOINT_NAMESPACE)?;
+            env.open_database("checkpoints")?
+        };
+        let mut tx = self.state.begin(CHECKPOINT_NAMESPACE)?;
+        tx.put(&db, source.id.as_bytes(), &seq.to_be_bytes())?;
+        tx.commit()
+    }
+
+    /// Reads back the last checkpointed sequence number for `source`, if
+    /// any. Used to derive the `from_seq` a `Source` is started with.
+    pub fn read(&self, source: &NodeHandle) -> Result<Option<u64>, ExecutionError> {
+        let db = {
+            let mut env = self.state.init_state_store(CHECKPOINT_NAMESPACE)?;
+            env.open_database("checkpoints")?
+        };
+        let tx = self.state.begin(CHECKPOINT_NAMESPACE)?;
+        match tx.get(&db, source.id.as_bytes())? {
+            Some(bytes) if bytes.len() == 8 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&bytes);
+                Ok(Some(u64::from_be_bytes(buf)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Lists every source that currently has a checkpoint recorded, along
+    /// with its last acknowledged sequence number.
+    pub fn list(&self, sources: &[NodeHandle]) -> Result<Vec<(NodeHandle, u64)>, ExecutionError> {
+        let mut out = Vec::new();
+        for source in sources {
+            if let Some(seq) = self.read(source)? {
+                out.push((source.clone(), seq));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Drops the checkpoint for `source`, so its next run starts from
+    /// `from_seq: None` again.
+    pub fn reset(&self, source: &NodeHandle) -> Result<(), ExecutionError> {
+        let db = {
+            let mut env = self.state.init_state_store(CHECKPOINT_NAMESPACE)?;
+            env.open_database("checkpoints")?
+        };
+        let mut tx = self.state.begin(CHECKPOINT_NAMESPACE)?;
+        tx.del(&db, source.id.as_bytes())?;
+        tx.commit()
+    }
+}
@@ -0,0 +1,61 @@
+pub use crate::dag::node::NodeType;
+use std::collections::HashMap;
+
+pub type PortHandle = u16;
+
+pub const DEFAULT_PORT_HANDLE: PortHandle = 0xffff;
+
+/// Identifies a node uniquely within a DAG. `ns` groups nodes that belong to
+/// the same sub-pipeline (e.g. all nodes generated for one SQL statement),
+/// `id` disambiguates within that namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    pub ns: Option<u16>,
+    pub id: String,
+}
+
+impl NodeHandle {
+    pub fn new(ns: Option<u16>, id: String) -> Self {
+        Self { ns, id }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Endpoint {
+    pub node: NodeHandle,
+    pub port: PortHandle,
+}
+
+impl Endpoint {
+    pub fn new(node: NodeHandle, port: PortHandle) -> Self {
+        Self { node, port }
+    }
+}
+
+pub struct Dag {
+    pub nodes: HashMap<NodeHandle, NodeType>,
+    pub edges: Vec<(Endpoint, Endpoint)>,
+}
+
+impl Dag {
+    pub fn new() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, node: NodeType, handle: NodeHandle) {
+        self.nodes.insert(handle, node);
+    }
+
+    pub fn connect(&mut self, from: Endpoint, to: Endpoint) {
+        self.edges.push((from, to));
+    }
+}
+
+impl Default for Dag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
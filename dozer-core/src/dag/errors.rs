@@ -0,0 +1,30 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ExecutionError {
+    InvalidNodeHandle(String),
+    InvalidPortHandle(u16),
+    InvalidOperation(String),
+    InternalStringError(String),
+    InternalError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionError::InvalidNodeHandle(h) => write!(f, "Invalid node handle: {}", h),
+            ExecutionError::InvalidPortHandle(p) => write!(f, "Invalid port handle: {}", p),
+            ExecutionError::InvalidOperation(e) => write!(f, "Invalid operation: {}", e),
+            ExecutionError::InternalStringError(e) => write!(f, "Internal error: {}", e),
+            ExecutionError::InternalError(e) => write!(f, "Internal error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+impl From<std::io::Error> for ExecutionError {
+    fn from(e: std::io::Error) -> Self {
+        ExecutionError::InternalError(Box::new(e))
+    }
+}
@@ -0,0 +1,481 @@
+use crate::dag::channels::{ProcessorChannelForwarder, SourceChannelForwarder};
+use crate::dag::checkpoint::{CheckpointStore, CHECKPOINT_NAMESPACE};
+use crate::dag::dag::{Dag, NodeHandle, PortHandle};
+use crate::dag::errors::ExecutionError;
+use crate::dag::metrics::{serve_metrics, MetricsRegistry, OperationKind};
+use crate::dag::node::{NodeType, Processor, Sink};
+use crate::state::lmdb::{LmdbStateStoreManager, StateStoreOptions};
+use crate::state::StateStoreManager;
+use crate::storage::common::{Database, Environment, RwTransaction};
+use dozer_types::types::Operation;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutorOptions {
+    /// Number of operations accumulated per source before the executor opens
+    /// a commit window across every downstream node.
+    pub commit_sz: u32,
+    pub channel_buffer_sz: usize,
+    /// Applies to the shared environment every processor/sink's state lives
+    /// in (see `STATE_NAMESPACE`), independently of `checkpoint_store`; e.g.
+    /// `SyncMode::NoSync` trades the durability of that state for throughput.
+    pub state_store: StateStoreOptions,
+    /// Applies only to the checkpoint namespace (`CHECKPOINT_NAMESPACE`),
+    /// which records each source's last acknowledged sequence number.
+    /// Defaults to `StateStoreOptions::default()`'s fsync'd `SyncMode::Safe`
+    /// regardless of what `state_store` is set to, since a lost checkpoint
+    /// silently replays already-committed work from further back than
+    /// necessary rather than merely costing a rebuildable cache.
+    pub checkpoint_store: StateStoreOptions,
+    /// If set, serve per-node metrics in Prometheus format at this address.
+    pub metrics_addr: Option<SocketAddr>,
+    /// Number of committed windows between durable checkpoints of each
+    /// source's acknowledged sequence number. `1` checkpoints every window.
+    pub checkpoint_interval: u32,
+}
+
+impl Default for ExecutorOptions {
+    fn default() -> Self {
+        Self {
+            commit_sz: 10_000,
+            channel_buffer_sz: 10_000,
+            state_store: StateStoreOptions::default(),
+            checkpoint_store: StateStoreOptions::default(),
+            metrics_addr: None,
+            checkpoint_interval: 1,
+        }
+    }
+}
+
+/// One channel transfer. `send` wraps its single op in a one-element batch
+/// so the receive side only has to handle one shape.
+type ChannelBatch = Vec<(u64, Operation, PortHandle)>;
+
+struct ChannelForwarder {
+    sender: Sender<ChannelBatch>,
+}
+
+impl SourceChannelForwarder for ChannelForwarder {
+    fn send(&mut self, seq: u64, op: Operation, port: PortHandle) -> Result<(), ExecutionError> {
+        self.sender
+            .send(vec![(seq, op, port)])
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+
+    fn send_batch(
+        &mut self,
+        ops: Vec<(u64, Operation)>,
+        port: PortHandle,
+    ) -> Result<(), ExecutionError> {
+        self.sender
+            .send(ops.into_iter().map(|(seq, op)| (seq, op, port)).collect())
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+}
+
+/// Collects whatever a `Processor::process` call forwards, so the executor
+/// can recurse into each op's downstream edges after the call returns
+/// instead of the processor owning the channel itself.
+struct CollectingForwarder {
+    emitted: Vec<(Operation, PortHandle)>,
+}
+
+impl ProcessorChannelForwarder for CollectingForwarder {
+    fn send(&mut self, op: Operation, port: PortHandle) -> Result<(), ExecutionError> {
+        self.emitted.push((op, port));
+        Ok(())
+    }
+}
+
+enum NodeRuntime {
+    Processor(Box<dyn Processor>),
+    Sink(Box<dyn Sink>),
+}
+
+/// Every node in the DAG is built/initialised against this same store name,
+/// so its sub-databases always live in the same physical LMDB environment
+/// that `run_window` opens its shared `RwTransaction` against (see
+/// `ensure_built`). A node can't be pinned to "its source's" environment
+/// instead, because a join node is fed by two different sources' windows,
+/// and the source a node happens to be reached through first would then
+/// decide an environment the node is stuck with for every later window
+/// reached via the other source. `start()` runs sources one at a time, so
+/// one shared environment for the whole DAG never has two writers racing it.
+const STATE_NAMESPACE: &str = "__dozer_state__";
+
+/// Prefixes the database names a node opens with its own id, so nodes
+/// sharing `STATE_NAMESPACE`'s one environment can't collide on a
+/// sub-database name.
+struct ScopedEnvironment<'a> {
+    inner: &'a mut dyn Environment,
+    node_id: &'a str,
+}
+
+impl<'a> Environment for ScopedEnvironment<'a> {
+    fn open_database(&mut self, name: &str) -> Result<Database, ExecutionError> {
+        self.inner.open_database(&format!("{}__{}", self.node_id, name))
+    }
+}
+
+/// Single-threaded reference executor. Every node downstream of a source
+/// shares one `RwTransaction` per commit window: the window is opened with
+/// `state.begin`, every processor/sink hop along `dag.edges` writes into it,
+/// and at the boundary it is either committed for the whole window or, if
+/// any node failed, aborted for the whole window and the error returned from
+/// `start`. The failed window is not retried within the run; the source's
+/// checkpoint is left where it was, so the next run starts the source from
+/// the last checkpointed sequence number.
+pub struct DagExecutor<'a> {
+    dag: &'a Dag,
+    state: Arc<LmdbStateStoreManager>,
+    checkpoints: CheckpointStore,
+    options: ExecutorOptions,
+    metrics: MetricsRegistry,
+    /// `(node, output_port) -> [(node, input_port)]`, derived once from
+    /// `dag.edges` so routing an op is a lookup instead of a DAG walk.
+    adjacency: HashMap<(NodeHandle, PortHandle), Vec<(NodeHandle, PortHandle)>>,
+    /// Every non-source node is built and `init`ialized at most once and
+    /// reused for the life of the executor, not re-built per op or window.
+    runtimes: RefCell<HashMap<NodeHandle, NodeRuntime>>,
+}
+
+impl<'a> DagExecutor<'a> {
+    pub fn new(
+        dag: &'a Dag,
+        base_path: &Path,
+        options: ExecutorOptions,
+    ) -> Result<Self, ExecutionError> {
+        let mut store_overrides = HashMap::new();
+        store_overrides.insert(CHECKPOINT_NAMESPACE.to_string(), options.checkpoint_store);
+        let state = Arc::new(LmdbStateStoreManager::new(
+            base_path,
+            options.state_store,
+            store_overrides,
+        )?);
+        let checkpoints = CheckpointStore::new(state.clone() as Arc<dyn StateStoreManager>);
+
+        let mut adjacency: HashMap<(NodeHandle, PortHandle), Vec<(NodeHandle, PortHandle)>> =
+            HashMap::new();
+        for (from, to) in &dag.edges {
+            adjacency
+                .entry((from.node.clone(), from.port))
+                .or_default()
+                .push((to.node.clone(), to.port));
+        }
+
+        Ok(Self {
+            dag,
+            state,
+            checkpoints,
+            options,
+            metrics: MetricsRegistry::new(),
+            adjacency,
+            runtimes: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Programmatic access to the same counters the `/metrics` endpoint
+    /// exposes, for callers embedding the executor rather than scraping it.
+    pub fn metrics_snapshot(&self) -> HashMap<NodeHandle, crate::dag::metrics::NodeMetricsSnapshot> {
+        self.metrics.snapshot()
+    }
+
+    /// Every source in the DAG with a durably recorded checkpoint, and the
+    /// sequence number it will resume from.
+    pub fn list_checkpoints(&self) -> Result<Vec<(NodeHandle, u64)>, ExecutionError> {
+        let sources: Vec<NodeHandle> = self
+            .dag
+            .nodes
+            .iter()
+            .filter(|(_, n)| matches!(n, NodeType::Source(_)))
+            .map(|(h, _)| h.clone())
+            .collect();
+        self.checkpoints.list(&sources)
+    }
+
+    /// Drops `source`'s checkpoint so its next run starts from scratch.
+    pub fn reset_checkpoint(&self, source: &NodeHandle) -> Result<(), ExecutionError> {
+        self.checkpoints.reset(source)
+    }
+
+    pub fn start(&mut self) -> Result<(), ExecutionError> {
+        if let Some(addr) = self.options.metrics_addr {
+            serve_metrics(self.metrics.clone(), addr)?;
+        }
+
+        // Every processor/sink is built up front, before any window's
+        // RwTransaction is opened: a node's `init` is free to call
+        // `Environment::open_database`, which opens its own short-lived
+        // write transaction on STATE_NAMESPACE's environment internally.
+        // Building lazily from inside `route()` would nest that against the
+        // window's already-open write transaction on the same environment
+        // and deadlock, the same hazard `dag/checkpoint.rs` avoids by never
+        // calling `open_database` while a transaction is open.
+        let node_handles: Vec<NodeHandle> = self
+            .dag
+            .nodes
+            .iter()
+            .filter(|(_, n)| !matches!(n, NodeType::Source(_)))
+            .map(|(h, _)| h.clone())
+            .collect();
+        for handle in &node_handles {
+            self.ensure_built(handle)?;
+        }
+
+        let sources: Vec<NodeHandle> = self
+            .dag
+            .nodes
+            .iter()
+            .filter(|(_, n)| matches!(n, NodeType::Source(_)))
+            .map(|(h, _)| h.clone())
+            .collect();
+        for handle in &sources {
+            let factory = match &self.dag.nodes[handle] {
+                NodeType::Source(f) => f.clone(),
+                _ => unreachable!(),
+            };
+            self.run_source(handle, factory.as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn run_source(
+        &self,
+        handle: &NodeHandle,
+        factory: &dyn crate::dag::node::SourceFactory,
+    ) -> Result<(), ExecutionError> {
+        let output_schemas = factory
+            .get_output_ports()
+            .iter()
+            .map(|p| Ok((p.handle, factory.get_output_schema(&p.handle)?)))
+            .collect::<Result<HashMap<_, _>, ExecutionError>>()?;
+        let source = factory.build(output_schemas)?;
+
+        let (sender, receiver): (Sender<ChannelBatch>, Receiver<ChannelBatch>) =
+            std::sync::mpsc::channel();
+
+        let mut last_committed_seq: Option<u64> = self.checkpoints.read(handle)?;
+        let from_seq = last_committed_seq;
+        let mut window: Vec<(u64, Operation, PortHandle)> = Vec::new();
+        let mut windows_since_checkpoint: u32 = 0;
+
+        std::thread::scope(|scope| -> Result<(), ExecutionError> {
+            // `fw` (and the `Sender` it owns) is moved into the thread so it's
+            // dropped as soon as the source finishes, which is what lets
+            // `receiver.iter()` below terminate instead of blocking forever.
+            let sender_thread = scope.spawn(move || {
+                let mut fw = ChannelForwarder { sender };
+                source.start(&mut fw, from_seq)
+            });
+
+            for batch in receiver.iter() {
+                for item in batch {
+                    let port = item.2;
+                    window.push(item);
+                    self.metrics.set_queue_depth(handle, port, window.len() as u64);
+
+                    if window.len() as u32 >= self.options.commit_sz {
+                        self.run_window(
+                            handle,
+                            &window,
+                            &mut last_committed_seq,
+                            &mut windows_since_checkpoint,
+                        )?;
+                        window.clear();
+                    }
+                }
+            }
+            if !window.is_empty() {
+                self.run_window(
+                    handle,
+                    &window,
+                    &mut last_committed_seq,
+                    &mut windows_since_checkpoint,
+                )?;
+            }
+
+            sender_thread
+                .join()
+                .map_err(|_| ExecutionError::InternalStringError("source thread panicked".into()))?
+        })
+    }
+
+    /// Opens one `RwTransaction` for the whole downstream chain, routes
+    /// every queued operation from `source` along `dag.edges` through each
+    /// processor hop down to the sinks it reaches, and either commits it for
+    /// all of them or rolls the entire window back.
+    fn run_window(
+        &self,
+        source: &NodeHandle,
+        window: &[(u64, Operation, PortHandle)],
+        last_committed_seq: &mut Option<u64>,
+        windows_since_checkpoint: &mut u32,
+    ) -> Result<(), ExecutionError> {
+        let mut tx = self.state.begin(STATE_NAMESPACE)?;
+        let mut touched_sinks: HashSet<NodeHandle> = HashSet::new();
+
+        let result = (|| -> Result<(), ExecutionError> {
+            for (seq, op, port) in window {
+                self.metrics.record_in(source, operation_kind(op));
+                self.route(source, *port, *seq, op.clone(), tx.as_mut(), &mut touched_sinks)?;
+            }
+            for sink in &touched_sinks {
+                if let Some(NodeRuntime::Sink(s)) = self.runtimes.borrow().get(sink) {
+                    s.commit(tx.as_mut())?;
+                }
+            }
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => {
+                tx.commit()?;
+                self.metrics.record_commit(source);
+                *last_committed_seq = window.last().map(|(seq, _, _)| *seq);
+
+                *windows_since_checkpoint += 1;
+                if let (true, Some(seq)) = (
+                    *windows_since_checkpoint >= self.options.checkpoint_interval,
+                    *last_committed_seq,
+                ) {
+                    self.checkpoints.checkpoint(source, seq)?;
+                    *windows_since_checkpoint = 0;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                tx.abort()?;
+                self.metrics.record_error(source);
+                Err(e)
+            }
+        }
+    }
+
+    /// Delivers `op` to every node wired to `(from, from_port)` in
+    /// `dag.edges`, recursing through processors until a sink is reached.
+    /// Every node visited here records its own metrics (inbound op, process
+    /// latency, errors, and true per-record output counts) against its own
+    /// `NodeHandle`, rather than the window's originating source — a join
+    /// downstream of two sources would otherwise have its work attributed to
+    /// whichever source happened to trigger it.
+    fn route(
+        &self,
+        from: &NodeHandle,
+        from_port: PortHandle,
+        seq: u64,
+        op: Operation,
+        tx: &mut dyn RwTransaction,
+        touched_sinks: &mut HashSet<NodeHandle>,
+    ) -> Result<(), ExecutionError> {
+        let targets = self
+            .adjacency
+            .get(&(from.clone(), from_port))
+            .cloned()
+            .unwrap_or_default();
+
+        for (node, port) in targets {
+            self.ensure_built(&node)?;
+            self.metrics.record_in(&node, operation_kind(&op));
+            let started = Instant::now();
+
+            match self.dag.nodes.get(&node) {
+                Some(NodeType::Processor(_)) => {
+                    let outcome = {
+                        let mut runtimes = self.runtimes.borrow_mut();
+                        let Some(NodeRuntime::Processor(p)) = runtimes.get_mut(&node) else {
+                            return Err(ExecutionError::InvalidNodeHandle(node.id.clone()));
+                        };
+                        let mut fw = CollectingForwarder {
+                            emitted: Vec::new(),
+                        };
+                        p.process(port, op.clone(), &mut fw, tx, &HashMap::new())
+                            .map(|_| fw.emitted)
+                    };
+                    self.metrics.observe_process(&node, started.elapsed());
+                    let emitted = match outcome {
+                        Ok(emitted) => emitted,
+                        Err(e) => {
+                            self.metrics.record_error(&node);
+                            return Err(e);
+                        }
+                    };
+                    for (out_op, out_port) in emitted {
+                        self.metrics.record_out(&node);
+                        self.route(&node, out_port, seq, out_op, tx, touched_sinks)?;
+                    }
+                }
+                Some(NodeType::Sink(_)) => {
+                    touched_sinks.insert(node.clone());
+                    let outcome = {
+                        let mut runtimes = self.runtimes.borrow_mut();
+                        let Some(NodeRuntime::Sink(s)) = runtimes.get_mut(&node) else {
+                            return Err(ExecutionError::InvalidNodeHandle(node.id.clone()));
+                        };
+                        s.process(port, seq, op.clone(), tx, &HashMap::new())
+                    };
+                    self.metrics.observe_process(&node, started.elapsed());
+                    if let Err(e) = outcome {
+                        self.metrics.record_error(&node);
+                        return Err(e);
+                    }
+                    self.metrics.record_out(&node);
+                }
+                Some(NodeType::Source(_)) | None => {
+                    return Err(ExecutionError::InvalidNodeHandle(node.id.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds and `init`s `node`'s `Processor`/`Sink` the first time it's
+    /// reached, caching it in `runtimes` for every later window. `node` is
+    /// initialised against `STATE_NAMESPACE`'s shared `Environment`, the same
+    /// one `run_window` opens its `RwTransaction` against — a `Database`
+    /// handle obtained here wouldn't be valid against that transaction
+    /// otherwise. `ScopedEnvironment` keeps different nodes from colliding on
+    /// a sub-database name within that shared environment.
+    fn ensure_built(&self, node: &NodeHandle) -> Result<(), ExecutionError> {
+        if self.runtimes.borrow().contains_key(node) {
+            return Ok(());
+        }
+        let mut env = self.state.init_state_store(STATE_NAMESPACE)?;
+        let mut scoped = ScopedEnvironment {
+            inner: env.as_mut(),
+            node_id: &node.id,
+        };
+        let runtime = match self.dag.nodes.get(node) {
+            Some(NodeType::Processor(factory)) => {
+                let mut p = factory.build();
+                p.init(&mut scoped)?;
+                NodeRuntime::Processor(p)
+            }
+            Some(NodeType::Sink(factory)) => {
+                let mut s = factory.build(HashMap::new())?;
+                s.init(&mut scoped)?;
+                NodeRuntime::Sink(s)
+            }
+            _ => return Err(ExecutionError::InvalidNodeHandle(node.id.clone())),
+        };
+        self.runtimes.borrow_mut().insert(node.clone(), runtime);
+        Ok(())
+    }
+
+    pub fn join(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+}
+
+fn operation_kind(op: &Operation) -> OperationKind {
+    match op {
+        Operation::Insert { .. } => OperationKind::Insert,
+        Operation::Update { .. } => OperationKind::Update,
+        Operation::Delete { .. } => OperationKind::Delete,
+    }
+}
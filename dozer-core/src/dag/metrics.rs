@@ -0,0 +1,231 @@
+use crate::dag::dag::NodeHandle;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Bucket boundaries (milliseconds) for the `process()` latency histogram,
+/// chosen to resolve both the sub-millisecond fast path and the slow-join
+/// tail without tracking every sample.
+const LATENCY_BUCKETS_MS: [f64; 9] = [0.1, 0.5, 1.0, 5.0, 10.0, 50.0, 100.0, 500.0, 1000.0];
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_MS.len()],
+    sum_ms: Mutex<f64>,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, d: Duration) {
+        let ms = d.as_secs_f64() * 1000.0;
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            if ms <= *bound {
+                self.buckets[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        *self.sum_ms.lock().unwrap() += ms;
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct NodeMetrics {
+    records_in: AtomicU64,
+    records_out: AtomicU64,
+    inserts: AtomicU64,
+    updates: AtomicU64,
+    deletes: AtomicU64,
+    errors: AtomicU64,
+    commits: AtomicU64,
+    process_latency: Histogram,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NodeMetricsSnapshot {
+    pub records_in: u64,
+    pub records_out: u64,
+    pub inserts: u64,
+    pub updates: u64,
+    pub deletes: u64,
+    pub errors: u64,
+    pub commits: u64,
+    pub process_count: u64,
+    pub process_sum_ms: f64,
+    /// Cumulative counts (observations `<= le`) for each of
+    /// `LATENCY_BUCKETS_MS`, in the same order.
+    pub process_buckets: [u64; LATENCY_BUCKETS_MS.len()],
+}
+
+/// In-memory counters for one DAG run, keyed by `NodeHandle` for node-level
+/// stats and by `(NodeHandle, PortHandle)` for per-edge queue depth. Cloned
+/// handles share the same underlying maps, so the HTTP scrape endpoint and
+/// the executor thread observe the same state.
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    nodes: Arc<Mutex<HashMap<NodeHandle, Arc<NodeMetrics>>>>,
+    queue_depth: Arc<Mutex<HashMap<(NodeHandle, u16), AtomicU64>>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn node(&self, handle: &NodeHandle) -> Arc<NodeMetrics> {
+        self.nodes
+            .lock()
+            .unwrap()
+            .entry(handle.clone())
+            .or_insert_with(|| Arc::new(NodeMetrics::default()))
+            .clone()
+    }
+
+    pub fn record_in(&self, handle: &NodeHandle, kind: OperationKind) {
+        let m = self.node(handle);
+        m.records_in.fetch_add(1, Ordering::Relaxed);
+        match kind {
+            OperationKind::Insert => m.inserts.fetch_add(1, Ordering::Relaxed),
+            OperationKind::Update => m.updates.fetch_add(1, Ordering::Relaxed),
+            OperationKind::Delete => m.deletes.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub fn record_out(&self, handle: &NodeHandle) {
+        self.node(handle).records_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self, handle: &NodeHandle) {
+        self.node(handle).errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_commit(&self, handle: &NodeHandle) {
+        self.node(handle).commits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn observe_process(&self, handle: &NodeHandle, elapsed: Duration) {
+        self.node(handle).process_latency.observe(elapsed);
+    }
+
+    pub fn set_queue_depth(&self, handle: &NodeHandle, port: u16, depth: u64) {
+        self.queue_depth
+            .lock()
+            .unwrap()
+            .entry((handle.clone(), port))
+            .or_insert_with(|| AtomicU64::new(0))
+            .store(depth, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> HashMap<NodeHandle, NodeMetricsSnapshot> {
+        self.nodes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(handle, m)| {
+                (
+                    handle.clone(),
+                    NodeMetricsSnapshot {
+                        records_in: m.records_in.load(Ordering::Relaxed),
+                        records_out: m.records_out.load(Ordering::Relaxed),
+                        inserts: m.inserts.load(Ordering::Relaxed),
+                        updates: m.updates.load(Ordering::Relaxed),
+                        deletes: m.deletes.load(Ordering::Relaxed),
+                        errors: m.errors.load(Ordering::Relaxed),
+                        commits: m.commits.load(Ordering::Relaxed),
+                        process_count: m.process_latency.count.load(Ordering::Relaxed),
+                        process_sum_ms: *m.process_latency.sum_ms.lock().unwrap(),
+                        process_buckets: std::array::from_fn(|i| {
+                            m.process_latency.buckets[i].load(Ordering::Relaxed)
+                        }),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Renders every counter/histogram in Prometheus text exposition format,
+    /// labelled by node (and port, for queue depth).
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        for (handle, s) in self.snapshot() {
+            let node = format!("node=\"{}\"", handle.id);
+            out.push_str(&format!("dozer_records_in_total{{{node}}} {}\n", s.records_in));
+            out.push_str(&format!("dozer_records_out_total{{{node}}} {}\n", s.records_out));
+            out.push_str(&format!(
+                "dozer_operations_total{{{node},op=\"insert\"}} {}\n",
+                s.inserts
+            ));
+            out.push_str(&format!(
+                "dozer_operations_total{{{node},op=\"update\"}} {}\n",
+                s.updates
+            ));
+            out.push_str(&format!(
+                "dozer_operations_total{{{node},op=\"delete\"}} {}\n",
+                s.deletes
+            ));
+            out.push_str(&format!("dozer_errors_total{{{node}}} {}\n", s.errors));
+            out.push_str(&format!("dozer_commits_total{{{node}}} {}\n", s.commits));
+            for (bound, count) in LATENCY_BUCKETS_MS.iter().zip(s.process_buckets.iter()) {
+                out.push_str(&format!(
+                    "dozer_process_latency_ms_bucket{{{node},le=\"{}\"}} {}\n",
+                    bound, count
+                ));
+            }
+            out.push_str(&format!(
+                "dozer_process_latency_ms_bucket{{{node},le=\"+Inf\"}} {}\n",
+                s.process_count
+            ));
+            out.push_str(&format!(
+                "dozer_process_latency_ms_sum{{{node}}} {}\n",
+                s.process_sum_ms
+            ));
+            out.push_str(&format!(
+                "dozer_process_latency_ms_count{{{node}}} {}\n",
+                s.process_count
+            ));
+        }
+        for ((handle, port), depth) in self.queue_depth.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "dozer_channel_queue_depth{{node=\"{}\",port=\"{}\"}} {}\n",
+                handle.id,
+                port,
+                depth.load(Ordering::Relaxed)
+            ));
+        }
+        out
+    }
+}
+
+/// Serves `render_prometheus()` at `GET /metrics` on a background thread.
+/// Minimal by design: one handler, no routing, no TLS — this is an
+/// operator-facing scrape target, not a public API.
+pub fn serve_metrics(registry: MetricsRegistry, addr: std::net::SocketAddr) -> std::io::Result<()> {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(addr)?;
+    std::thread::Builder::new()
+        .name("dozer-metrics".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let body = registry.render_prometheus();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        })?;
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod channels;
+pub mod checkpoint;
+#[allow(clippy::module_inception)]
+pub mod dag;
+pub mod errors;
+pub mod executor;
+pub mod metrics;
+pub mod node;
+pub mod record_store;
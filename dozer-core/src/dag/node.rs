@@ -0,0 +1,105 @@
+use crate::dag::channels::SourceChannelForwarder;
+use crate::dag::dag::PortHandle;
+use crate::dag::errors::ExecutionError;
+use crate::dag::record_store::RecordReader;
+use crate::storage::common::{Environment, RwTransaction};
+use dozer_types::types::{Operation, Schema};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputPortDefOptions {
+    pub stateful: bool,
+}
+
+impl Default for OutputPortDefOptions {
+    fn default() -> Self {
+        Self { stateful: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutputPortDef {
+    pub handle: PortHandle,
+    pub options: OutputPortDefOptions,
+}
+
+impl OutputPortDef {
+    pub fn new(handle: PortHandle, options: OutputPortDefOptions) -> Self {
+        Self { handle, options }
+    }
+}
+
+pub trait SourceFactory: Send + Sync {
+    fn get_output_ports(&self) -> Vec<OutputPortDef>;
+    fn get_output_schema(&self, port: &PortHandle) -> Result<Schema, ExecutionError>;
+    fn build(
+        &self,
+        output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Source>, ExecutionError>;
+}
+
+pub trait Source: Send + Sync {
+    fn start(
+        &self,
+        fw: &mut dyn SourceChannelForwarder,
+        from_seq: Option<u64>,
+    ) -> Result<(), ExecutionError>;
+}
+
+pub trait SinkFactory: Send + Sync {
+    fn get_input_ports(&self) -> Vec<PortHandle>;
+    fn set_input_schema(
+        &self,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<(), ExecutionError>;
+    fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Sink>, ExecutionError>;
+}
+
+pub trait Sink {
+    fn init(&mut self, env: &mut dyn Environment) -> Result<(), ExecutionError>;
+
+    fn process(
+        &mut self,
+        from_port: PortHandle,
+        seq: u64,
+        op: Operation,
+        state: &mut dyn RwTransaction,
+        reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError>;
+
+    /// Flushes the work accumulated in `state` since the last commit. Called
+    /// once per batch window by the executor, never mid-batch.
+    fn commit(&self, tx: &mut dyn RwTransaction) -> Result<(), ExecutionError>;
+}
+
+pub trait ProcessorFactory: Send + Sync {
+    fn get_output_ports(&self) -> Vec<OutputPortDef>;
+    fn get_output_schema(
+        &self,
+        output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, ExecutionError>;
+    fn build(&self) -> Box<dyn Processor>;
+}
+
+pub trait Processor {
+    fn init(&mut self, env: &mut dyn Environment) -> Result<(), ExecutionError>;
+
+    fn process(
+        &mut self,
+        from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn crate::dag::channels::ProcessorChannelForwarder,
+        state: &mut dyn RwTransaction,
+        reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError>;
+}
+
+pub enum NodeType {
+    Source(std::sync::Arc<dyn SourceFactory>),
+    Processor(std::sync::Arc<dyn ProcessorFactory>),
+    Sink(std::sync::Arc<dyn SinkFactory>),
+}
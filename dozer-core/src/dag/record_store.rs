@@ -0,0 +1,17 @@
+use dozer_types::types::Record;
+
+/// Read-only handle onto an in-memory list of records for a given input
+/// port. `DagExecutor` passes an empty reader map to every `process` call.
+pub struct RecordReader {
+    records: Vec<Record>,
+}
+
+impl RecordReader {
+    pub fn new(records: Vec<Record>) -> Self {
+        Self { records }
+    }
+
+    pub fn get_all(&self) -> &[Record] {
+        &self.records
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dag;
+pub mod state;
+pub mod storage;
@@ -0,0 +1,243 @@
+use crate::dag::errors::ExecutionError;
+use crate::storage::common::{Database, Environment, RwTransaction};
+use lmdb::{EnvironmentFlags, Transaction, WriteFlags};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How aggressively the store fsyncs on commit. `Safe` is the correct
+/// default for sinks; `NoSync`/`NoMetaSync` trade durability for throughput
+/// and are meant for intermediate/aggregation state that can be rebuilt by
+/// replaying from the last source checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Full fsync of data and metadata on every commit.
+    Safe,
+    /// Skip the fsync on commit (`MDB_NOSYNC`); neither data nor metadata
+    /// is flushed.
+    NoSync,
+    /// Skip both the data and metadata fsync (`MDB_NOSYNC | MDB_NOMETASYNC`).
+    NoMetaSync,
+}
+
+impl SyncMode {
+    fn flags(self) -> EnvironmentFlags {
+        match self {
+            SyncMode::Safe => EnvironmentFlags::empty(),
+            SyncMode::NoSync => EnvironmentFlags::NO_SYNC,
+            SyncMode::NoMetaSync => EnvironmentFlags::NO_SYNC | EnvironmentFlags::NO_META_SYNC,
+        }
+    }
+}
+
+/// The map never resizes once opened: the installed `lmdb` crate only
+/// exposes `set_map_size` on the pre-open `EnvironmentBuilder`, not on an
+/// already-open `Environment`, so there's no safe way to grow it in place.
+/// A write that hits `MDB_MAP_FULL` surfaces as an error; size `map_size`
+/// generously up front.
+#[derive(Debug, Clone, Copy)]
+pub struct StateStoreOptions {
+    pub sync_mode: SyncMode,
+    pub map_size: usize,
+    pub max_readers: u32,
+    /// How long a `begin` waits on the writer lock before giving up.
+    pub lock_timeout: Duration,
+}
+
+impl Default for StateStoreOptions {
+    fn default() -> Self {
+        Self {
+            sync_mode: SyncMode::Safe,
+            map_size: 1024 * 1024 * 1024,
+            max_readers: 256,
+            lock_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Owns one `lmdb::Environment` per store name, opened lazily on first use
+/// and kept alive for the manager's whole lifetime. LMDB environments are
+/// expensive to open (they mmap the data file and allocate the reader
+/// table) and are already safe to share across threads/transactions, so
+/// re-opening one on every `init_state_store`/`begin` call — as an earlier
+/// version of this did — just wastes file descriptors and memory mappings.
+///
+/// `sync_mode` (and the rest of `StateStoreOptions`) is an LMDB environment
+/// flag, so it can only be set per physical environment, not per
+/// sub-database within one — two nodes sharing an environment always share
+/// its durability. `overrides` is how a caller still differentiates, e.g.
+/// pinning the checkpoint namespace to a fsync'd `Safe` environment while
+/// the shared processing-state environment runs `NoSync` for throughput.
+/// Any store name absent from `overrides` falls back to `default_options`.
+pub struct LmdbStateStoreManager {
+    base_path: std::path::PathBuf,
+    default_options: StateStoreOptions,
+    overrides: HashMap<String, StateStoreOptions>,
+    envs: Mutex<HashMap<String, Arc<lmdb::Environment>>>,
+}
+
+impl LmdbStateStoreManager {
+    pub fn new(
+        base_path: &Path,
+        default_options: StateStoreOptions,
+        overrides: HashMap<String, StateStoreOptions>,
+    ) -> Result<Self, ExecutionError> {
+        std::fs::create_dir_all(base_path)?;
+        Ok(Self {
+            base_path: base_path.to_path_buf(),
+            default_options,
+            overrides,
+            envs: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn options_for(&self, name: &str) -> StateStoreOptions {
+        self.overrides
+            .get(name)
+            .copied()
+            .unwrap_or(self.default_options)
+    }
+
+    fn env(&self, name: &str) -> Result<Arc<lmdb::Environment>, ExecutionError> {
+        let mut envs = self.envs.lock().unwrap();
+        if let Some(env) = envs.get(name) {
+            return Ok(env.clone());
+        }
+        let options = self.options_for(name);
+        let path = self.base_path.join(name);
+        std::fs::create_dir_all(&path)?;
+        let env = Arc::new(
+            lmdb::Environment::new()
+                .set_map_size(options.map_size)
+                .set_max_dbs(16)
+                .set_max_readers(options.max_readers)
+                .set_flags(options.sync_mode.flags())
+                .open(&path)
+                .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
+        );
+        envs.insert(name.to_string(), env.clone());
+        Ok(env)
+    }
+}
+
+impl super::StateStoreManager for LmdbStateStoreManager {
+    fn init_state_store(&self, name: &str) -> Result<Box<dyn Environment>, ExecutionError> {
+        let env = self.env(name)?;
+        Ok(Box::new(LmdbEnvironment { env }))
+    }
+
+    fn begin(&self, name: &str) -> Result<Box<dyn RwTransaction>, ExecutionError> {
+        let env = self.env(name)?;
+        let lock_timeout = self.options_for(name).lock_timeout;
+        let started = std::time::Instant::now();
+        let tx = loop {
+            match env.begin_rw_txn() {
+                Ok(tx) => break tx,
+                Err(lmdb::Error::Other(11)) if started.elapsed() < lock_timeout => {
+                    // EAGAIN: writer lock is held by another transaction, retry until lock_timeout.
+                    std::thread::yield_now();
+                }
+                Err(e) => return Err(ExecutionError::InternalError(Box::new(e))),
+            }
+        };
+        // `tx` borrows `env` for `'env`. We keep `env` alive for at least as
+        // long via the `Arc` stored alongside it and erase the borrow to
+        // `'static` so the two can live in the same struct; `tx` is declared
+        // before `env` below so it's dropped first, before the environment it
+        // borrows from.
+        let tx: lmdb::RwTransaction<'static> = unsafe { std::mem::transmute(tx) };
+        Ok(Box::new(LmdbRwTransaction {
+            tx: Some(tx),
+            env,
+        }))
+    }
+}
+
+pub struct LmdbEnvironment {
+    env: Arc<lmdb::Environment>,
+}
+
+impl Environment for LmdbEnvironment {
+    fn open_database(&mut self, name: &str) -> Result<Database, ExecutionError> {
+        self.env
+            .create_db(Some(name), lmdb::DatabaseFlags::empty())
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        Ok(Database(name.to_string()))
+    }
+}
+
+/// Wraps one LMDB write transaction. `abort` is the rollback surface the
+/// executor drives when a node in the current batch window fails; `commit`
+/// is what it drives when every node in the window succeeded.
+///
+/// Field order matters: `tx` borrows from `env` (its `'static` lifetime is a
+/// lie we enforce ourselves, see `LmdbStateStoreManager::begin`), so it must
+/// be dropped before `env` — Rust drops struct fields in declaration order.
+pub struct LmdbRwTransaction {
+    tx: Option<lmdb::RwTransaction<'static>>,
+    env: Arc<lmdb::Environment>,
+}
+
+impl LmdbRwTransaction {
+    fn db_handle(&self, db: &Database) -> Result<lmdb::Database, ExecutionError> {
+        self.env
+            .open_db(Some(&db.0))
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+}
+
+impl RwTransaction for LmdbRwTransaction {
+    fn put(&mut self, db: &Database, key: &[u8], value: &[u8]) -> Result<(), ExecutionError> {
+        let handle = self.db_handle(db)?;
+        self.tx
+            .as_mut()
+            .expect("transaction already finalized")
+            .put(handle, &key, &value, WriteFlags::empty())
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+
+    fn del(&mut self, db: &Database, key: &[u8]) -> Result<bool, ExecutionError> {
+        let handle = self.db_handle(db)?;
+        match self
+            .tx
+            .as_mut()
+            .expect("transaction already finalized")
+            .del(handle, &key, None)
+        {
+            Ok(()) => Ok(true),
+            Err(lmdb::Error::NotFound) => Ok(false),
+            Err(e) => Err(ExecutionError::InternalError(Box::new(e))),
+        }
+    }
+
+    fn get(&self, db: &Database, key: &[u8]) -> Result<Option<Vec<u8>>, ExecutionError> {
+        let handle = self.db_handle(db)?;
+        match self
+            .tx
+            .as_ref()
+            .expect("transaction already finalized")
+            .get(handle, &key)
+        {
+            Ok(v) => Ok(Some(v.to_vec())),
+            Err(lmdb::Error::NotFound) => Ok(None),
+            Err(e) => Err(ExecutionError::InternalError(Box::new(e))),
+        }
+    }
+
+    fn commit(&mut self) -> Result<(), ExecutionError> {
+        self.tx
+            .take()
+            .expect("transaction already finalized")
+            .commit()
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+
+    fn abort(&mut self) -> Result<(), ExecutionError> {
+        self.tx
+            .take()
+            .expect("transaction already finalized")
+            .abort();
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+pub mod lmdb;
+
+use crate::dag::errors::ExecutionError;
+use crate::storage::common::{Environment, RwTransaction};
+use std::path::Path;
+
+/// Opens/owns the on-disk stores backing the nodes of a DAG, one per store
+/// name. Implementations (currently just LMDB) are responsible for handing
+/// out an `Environment` to register a node's sub-databases, and a fresh
+/// `RwTransaction` per batch window.
+pub trait StateStoreManager: Send + Sync {
+    fn init_state_store(&self, name: &str) -> Result<Box<dyn Environment>, ExecutionError>;
+    fn begin(&self, name: &str) -> Result<Box<dyn RwTransaction>, ExecutionError>;
+}
+
+pub trait StateStoreManagerFactory {
+    fn create(&self, base_path: &Path) -> Result<Box<dyn StateStoreManager>, ExecutionError>;
+}
@@ -0,0 +1,31 @@
+use crate::dag::errors::ExecutionError;
+
+/// A handle to a store's keyspace, used to open the named sub-databases a
+/// node needs before it starts receiving operations.
+pub trait Environment {
+    fn open_database(&mut self, name: &str) -> Result<Database, ExecutionError>;
+}
+
+/// The name of a sub-database within a node's keyspace. Kept as a name
+/// rather than a backend-specific handle so this abstraction doesn't leak
+/// LMDB's `MDB_dbi` (or any other backend's handle type) outside `state::`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Database(pub String);
+
+/// A single logical unit of work against the state store. Every node
+/// downstream of a source writes into the same `RwTransaction` for the
+/// duration of a batch window; it is either committed for all of them at
+/// once or aborted for all of them at once.
+pub trait RwTransaction {
+    fn put(&mut self, db: &Database, key: &[u8], value: &[u8]) -> Result<(), ExecutionError>;
+    fn del(&mut self, db: &Database, key: &[u8]) -> Result<bool, ExecutionError>;
+    fn get(&self, db: &Database, key: &[u8]) -> Result<Option<Vec<u8>>, ExecutionError>;
+
+    /// Commits every `put`/`del` issued since the transaction was opened.
+    /// The transaction cannot be used again afterwards.
+    fn commit(&mut self) -> Result<(), ExecutionError>;
+
+    /// Discards every `put`/`del` issued since the transaction was opened,
+    /// leaving the store exactly as it was before the batch began.
+    fn abort(&mut self) -> Result<(), ExecutionError>;
+}
@@ -0,0 +1,365 @@
+use crate::pipeline::processor::aggregation::AggregationProcessorFactory;
+use crate::pipeline::processor::projection::ProjectionProcessorFactory;
+use crate::pipeline::processor::selection::SelectionProcessorFactory;
+use crate::pipeline::product::join::{JoinProcessorFactory, JoinType, LEFT_JOIN_PORT, RIGHT_JOIN_PORT};
+use dozer_core::dag::dag::{Dag, Endpoint, NodeHandle, NodeType, DEFAULT_PORT_HANDLE};
+use dozer_core::dag::errors::ExecutionError;
+use dozer_core::dag::node::ProcessorFactory;
+use dozer_types::types::Schema;
+use sqlparser::ast::{
+    Expr, Ident, Join, JoinConstraint, JoinOperator, Query, Select, SetExpr, Statement,
+    TableFactor, TableWithJoins,
+};
+use std::collections::HashMap;
+
+/// Compiles one parsed SQL statement into a DAG fragment: a join node (if the
+/// `FROM` clause has a join), a selection node (if `WHERE` is present), a
+/// projection node for the `SELECT` list, and an aggregation node (if
+/// `GROUP BY`/`HAVING` is present), wired in that order. The caller connects
+/// the returned input endpoints to its own source nodes and the output
+/// endpoint to its own sink.
+///
+/// `table_schemas` must carry the output schema of every table named in the
+/// `FROM`/`JOIN` clauses (e.g. from the corresponding source factory's
+/// `get_output_schema`), since this is what lets the builder resolve column
+/// names in `ON`/`GROUP BY` and the `SELECT` list to field indexes ahead of
+/// time; `WHERE`/`HAVING` column names are resolved against the same schema
+/// each time a record is evaluated.
+pub struct PipelineBuilder {
+    ns: Option<u16>,
+}
+
+impl PipelineBuilder {
+    pub fn new(ns: Option<u16>) -> Self {
+        Self { ns }
+    }
+
+    fn node(&self, id: &str) -> NodeHandle {
+        NodeHandle::new(self.ns, id.to_string())
+    }
+
+    pub fn statement_to_pipeline(
+        &self,
+        statement: Statement,
+        table_schemas: &HashMap<String, Schema>,
+    ) -> Result<(Dag, HashMap<String, Endpoint>, Endpoint), ExecutionError> {
+        let select = match statement {
+            Statement::Query(query) => extract_select(*query)?,
+            _ => {
+                return Err(ExecutionError::InvalidOperation(
+                    "only SELECT statements are supported".into(),
+                ))
+            }
+        };
+
+        let mut dag = Dag::new();
+        let mut in_handle = HashMap::new();
+
+        let table = select
+            .from
+            .first()
+            .ok_or_else(|| ExecutionError::InvalidOperation("missing FROM clause".into()))?;
+
+        // For a join, the join node itself is already registered in `dag` (by
+        // `add_join_node`), so a WHERE clause becomes a new node downstream of
+        // it. For a single table there's no node yet at all — the caller's
+        // source connects directly into whatever processes it first, so that
+        // first node (selection if there's a WHERE clause, projection
+        // otherwise) doubles as `in_handle`'s entry point instead of a
+        // separate placeholder node nothing ever builds.
+        let (mut last, mut schema) = if let Some(join) = table.joins.first() {
+            let (join_handle, join_schema) =
+                self.add_join_node(&mut dag, &mut in_handle, table, join, table_schemas)?;
+
+            if let Some(predicate) = select.selection.clone() {
+                let selection_handle = self.node("selection");
+                dag.add_node(
+                    NodeType::Processor(std::sync::Arc::new(SelectionProcessorFactory::new(
+                        predicate,
+                        join_schema.clone(),
+                    ))),
+                    selection_handle.clone(),
+                );
+                dag.connect(
+                    Endpoint::new(join_handle, DEFAULT_PORT_HANDLE),
+                    Endpoint::new(selection_handle.clone(), DEFAULT_PORT_HANDLE),
+                );
+                (selection_handle, join_schema)
+            } else {
+                (join_handle, join_schema)
+            }
+        } else {
+            let name = table_name(&table.relation)?;
+            let schema = table_schema(table_schemas, &name)?;
+
+            let entry_handle = if let Some(predicate) = select.selection.clone() {
+                let handle = self.node(&format!("selection_{}", name));
+                dag.add_node(
+                    NodeType::Processor(std::sync::Arc::new(SelectionProcessorFactory::new(
+                        predicate,
+                        schema.clone(),
+                    ))),
+                    handle.clone(),
+                );
+                handle
+            } else {
+                self.node("projection")
+            };
+            in_handle.insert(name, Endpoint::new(entry_handle.clone(), DEFAULT_PORT_HANDLE));
+            (entry_handle, schema)
+        };
+
+        let projection_handle = self.node("projection");
+        let projection_factory =
+            ProjectionProcessorFactory::new(select.projection.clone(), schema.clone())?;
+        schema = projection_factory.output_schema().clone();
+        dag.add_node(
+            NodeType::Processor(std::sync::Arc::new(projection_factory)),
+            projection_handle.clone(),
+        );
+        if last != projection_handle {
+            dag.connect(
+                Endpoint::new(last, DEFAULT_PORT_HANDLE),
+                Endpoint::new(projection_handle.clone(), DEFAULT_PORT_HANDLE),
+            );
+        }
+        last = projection_handle;
+
+        if !select.group_by.is_empty() || select.having.is_some() {
+            let aggregation_handle = self.node("aggregation");
+            dag.add_node(
+                NodeType::Processor(std::sync::Arc::new(AggregationProcessorFactory::new(
+                    select.group_by.clone(),
+                    select.having.clone(),
+                    schema,
+                ))),
+                aggregation_handle.clone(),
+            );
+            dag.connect(
+                Endpoint::new(last, DEFAULT_PORT_HANDLE),
+                Endpoint::new(aggregation_handle.clone(), DEFAULT_PORT_HANDLE),
+            );
+            last = aggregation_handle;
+        }
+
+        Ok((dag, in_handle, Endpoint::new(last, DEFAULT_PORT_HANDLE)))
+    }
+
+    fn add_join_node(
+        &self,
+        dag: &mut Dag,
+        in_handle: &mut HashMap<String, Endpoint>,
+        table: &TableWithJoins,
+        join: &Join,
+        table_schemas: &HashMap<String, Schema>,
+    ) -> Result<(NodeHandle, Schema), ExecutionError> {
+        let left_name = table_name(&table.relation)?;
+        let right_name = table_name(&join.relation)?;
+        let left_schema = table_schema(table_schemas, &left_name)?;
+        let right_schema = table_schema(table_schemas, &right_name)?;
+
+        let (left_key, right_key, join_type) =
+            join_condition(join, &left_name, &left_schema, &right_name, &right_schema)?;
+
+        let left_source = self.node(&left_name);
+        let right_source = self.node(&right_name);
+        let join_handle = self.node(&format!("join_{}_{}", left_name, right_name));
+
+        let join_factory = JoinProcessorFactory::new(
+            left_source,
+            right_source,
+            left_key,
+            right_key,
+            join_type,
+            right_schema.fields.len(),
+        );
+        let mut input_schemas = HashMap::new();
+        input_schemas.insert(LEFT_JOIN_PORT, left_schema);
+        input_schemas.insert(RIGHT_JOIN_PORT, right_schema);
+        let output_schema =
+            join_factory.get_output_schema(&DEFAULT_PORT_HANDLE, &input_schemas)?;
+
+        dag.add_node(
+            NodeType::Processor(std::sync::Arc::new(join_factory)),
+            join_handle.clone(),
+        );
+
+        in_handle.insert(
+            left_name,
+            Endpoint::new(join_handle.clone(), LEFT_JOIN_PORT),
+        );
+        in_handle.insert(
+            right_name,
+            Endpoint::new(join_handle.clone(), RIGHT_JOIN_PORT),
+        );
+
+        Ok((join_handle, output_schema))
+    }
+}
+
+fn extract_select(query: Query) -> Result<Select, ExecutionError> {
+    match query.body {
+        SetExpr::Select(select) => Ok(*select),
+        _ => Err(ExecutionError::InvalidOperation(
+            "only simple SELECT queries are supported".into(),
+        )),
+    }
+}
+
+fn table_name(table: &TableFactor) -> Result<String, ExecutionError> {
+    match table {
+        TableFactor::Table { name, .. } => Ok(name.to_string()),
+        _ => Err(ExecutionError::InvalidOperation(
+            "only plain table references are supported".into(),
+        )),
+    }
+}
+
+fn table_schema(
+    table_schemas: &HashMap<String, Schema>,
+    name: &str,
+) -> Result<Schema, ExecutionError> {
+    table_schemas
+        .get(name)
+        .cloned()
+        .ok_or_else(|| ExecutionError::InvalidOperation(format!("unknown table `{}`", name)))
+}
+
+/// A column reference with an optional table qualifier, e.g. `id` or
+/// `Departments.id`.
+struct ColumnRef {
+    table: Option<String>,
+    column: String,
+}
+
+fn column_ref(expr: &Expr) -> Result<ColumnRef, ExecutionError> {
+    match expr {
+        Expr::Identifier(Ident { value, .. }) => Ok(ColumnRef {
+            table: None,
+            column: value.clone(),
+        }),
+        Expr::CompoundIdentifier(parts) => {
+            let column = parts
+                .last()
+                .ok_or_else(|| ExecutionError::InvalidOperation("empty column reference".into()))?
+                .value
+                .clone();
+            let table = parts.first().map(|i| i.value.clone());
+            Ok(ColumnRef { table, column })
+        }
+        other => Err(ExecutionError::InvalidOperation(format!(
+            "expected a column reference in join condition, got `{}`",
+            other
+        ))),
+    }
+}
+
+/// Resolves a column reference to its field index against whichever of
+/// `left_name`/`right_name`'s schema it names: the table qualifier, when
+/// present, picks the side directly; a bare column name is resolved against
+/// whichever single side actually has it.
+fn resolve_join_side(
+    col: &ColumnRef,
+    left_name: &str,
+    left_schema: &Schema,
+    right_name: &str,
+    right_schema: &Schema,
+) -> Result<(bool, usize), ExecutionError> {
+    let is_left = match &col.table {
+        Some(t) if t == left_name => true,
+        Some(t) if t == right_name => false,
+        Some(t) => {
+            return Err(ExecutionError::InvalidOperation(format!(
+                "join condition references unknown table `{}`",
+                t
+            )))
+        }
+        None => {
+            let in_left = left_schema.fields.iter().any(|f| f.name == col.column);
+            let in_right = right_schema.fields.iter().any(|f| f.name == col.column);
+            match (in_left, in_right) {
+                (true, false) => true,
+                (false, true) => false,
+                (true, true) => {
+                    return Err(ExecutionError::InvalidOperation(format!(
+                        "column `{}` is ambiguous between `{}` and `{}`",
+                        col.column, left_name, right_name
+                    )))
+                }
+                (false, false) => {
+                    return Err(ExecutionError::InvalidOperation(format!(
+                        "unknown column `{}`",
+                        col.column
+                    )))
+                }
+            }
+        }
+    };
+
+    let schema = if is_left { left_schema } else { right_schema };
+    let index = schema
+        .fields
+        .iter()
+        .position(|f| f.name == col.column)
+        .ok_or_else(|| ExecutionError::InvalidOperation(format!("unknown column `{}`", col.column)))?;
+    Ok((is_left, index))
+}
+
+/// Resolves `a.key = b.key` out of the join's `ON` clause into the field
+/// index on each side, and maps the SQL join keyword onto our `JoinType`.
+fn join_condition(
+    join: &Join,
+    left_name: &str,
+    left_schema: &Schema,
+    right_name: &str,
+    right_schema: &Schema,
+) -> Result<(usize, usize, JoinType), ExecutionError> {
+    let (constraint, join_type) = match &join.join_operator {
+        JoinOperator::Inner(c) => (c, JoinType::Inner),
+        JoinOperator::LeftOuter(c) => (c, JoinType::Left),
+        _ => {
+            return Err(ExecutionError::InvalidOperation(
+                "only INNER and LEFT joins are supported".into(),
+            ))
+        }
+    };
+
+    let expr = match constraint {
+        JoinConstraint::On(expr) => expr,
+        _ => {
+            return Err(ExecutionError::InvalidOperation(
+                "only ON join constraints are supported".into(),
+            ))
+        }
+    };
+
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: sqlparser::ast::BinaryOperator::Eq,
+            right,
+        } => {
+            let left_col = column_ref(left)?;
+            let right_col = column_ref(right)?;
+            let (left_is_left, left_idx) =
+                resolve_join_side(&left_col, left_name, left_schema, right_name, right_schema)?;
+            let (right_is_left, right_idx) =
+                resolve_join_side(&right_col, left_name, left_schema, right_name, right_schema)?;
+
+            if left_is_left == right_is_left {
+                return Err(ExecutionError::InvalidOperation(
+                    "join condition must reference one column from each side".into(),
+                ));
+            }
+
+            if left_is_left {
+                Ok((left_idx, right_idx, join_type))
+            } else {
+                Ok((right_idx, left_idx, join_type))
+            }
+        }
+        _ => Err(ExecutionError::InvalidOperation(
+            "only equi-join conditions are supported".into(),
+        )),
+    }
+}
@@ -0,0 +1,113 @@
+use dozer_core::dag::errors::ExecutionError;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, Record};
+
+/// Field type tags used by `encode_field`/`decode_field` to round-trip a
+/// `Record` through the state store's raw byte storage. Shared by every
+/// processor that needs to persist records/keys byte-for-byte, e.g. the join
+/// processor's side indexes and the aggregation processor's group state.
+const FIELD_TAG_INT: u8 = 0;
+const FIELD_TAG_FLOAT: u8 = 1;
+const FIELD_TAG_BOOLEAN: u8 = 2;
+const FIELD_TAG_STRING: u8 = 3;
+const FIELD_TAG_BINARY: u8 = 4;
+const FIELD_TAG_NULL: u8 = 5;
+
+pub(crate) fn encode_field(field: &Field, out: &mut Vec<u8>) {
+    match field {
+        Field::Int(v) => {
+            out.push(FIELD_TAG_INT);
+            out.extend_from_slice(&v.to_be_bytes());
+        }
+        Field::Float(v) => {
+            out.push(FIELD_TAG_FLOAT);
+            out.extend_from_slice(&v.0.to_be_bytes());
+        }
+        Field::Boolean(v) => {
+            out.push(FIELD_TAG_BOOLEAN);
+            out.push(*v as u8);
+        }
+        Field::String(v) => {
+            out.push(FIELD_TAG_STRING);
+            out.extend_from_slice(&(v.len() as u32).to_be_bytes());
+            out.extend_from_slice(v.as_bytes());
+        }
+        Field::Binary(v) => {
+            out.push(FIELD_TAG_BINARY);
+            out.extend_from_slice(&(v.len() as u32).to_be_bytes());
+            out.extend_from_slice(v);
+        }
+        Field::Null => out.push(FIELD_TAG_NULL),
+    }
+}
+
+pub(crate) fn decode_field(bytes: &[u8], pos: &mut usize) -> Result<Field, ExecutionError> {
+    let tag = *bytes
+        .get(*pos)
+        .ok_or_else(|| ExecutionError::InternalStringError("truncated encoded entry".into()))?;
+    *pos += 1;
+    match tag {
+        FIELD_TAG_INT => {
+            let v = i64::from_be_bytes(read_n(bytes, pos)?);
+            Ok(Field::Int(v))
+        }
+        FIELD_TAG_FLOAT => {
+            let v = f64::from_be_bytes(read_n(bytes, pos)?);
+            Ok(Field::Float(OrderedFloat(v)))
+        }
+        FIELD_TAG_BOOLEAN => {
+            let v = *bytes
+                .get(*pos)
+                .ok_or_else(|| ExecutionError::InternalStringError("truncated encoded entry".into()))?;
+            *pos += 1;
+            Ok(Field::Boolean(v != 0))
+        }
+        FIELD_TAG_STRING => {
+            let s = read_len_prefixed(bytes, pos)?;
+            Ok(Field::String(String::from_utf8(s).map_err(|e| {
+                ExecutionError::InternalError(Box::new(e))
+            })?))
+        }
+        FIELD_TAG_BINARY => Ok(Field::Binary(read_len_prefixed(bytes, pos)?)),
+        FIELD_TAG_NULL => Ok(Field::Null),
+        other => Err(ExecutionError::InternalStringError(format!(
+            "unknown encoded field tag {}",
+            other
+        ))),
+    }
+}
+
+pub(crate) fn read_n<const N: usize>(bytes: &[u8], pos: &mut usize) -> Result<[u8; N], ExecutionError> {
+    let slice = bytes
+        .get(*pos..*pos + N)
+        .ok_or_else(|| ExecutionError::InternalStringError("truncated encoded entry".into()))?;
+    *pos += N;
+    let mut out = [0u8; N];
+    out.copy_from_slice(slice);
+    Ok(out)
+}
+
+pub(crate) fn read_len_prefixed(bytes: &[u8], pos: &mut usize) -> Result<Vec<u8>, ExecutionError> {
+    let len = u32::from_be_bytes(read_n(bytes, pos)?) as usize;
+    let slice = bytes
+        .get(*pos..*pos + len)
+        .ok_or_else(|| ExecutionError::InternalStringError("truncated encoded entry".into()))?;
+    *pos += len;
+    Ok(slice.to_vec())
+}
+
+pub(crate) fn encode_record(record: &Record, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(record.values.len() as u32).to_be_bytes());
+    for v in &record.values {
+        encode_field(v, out);
+    }
+}
+
+pub(crate) fn decode_record(bytes: &[u8], pos: &mut usize) -> Result<Record, ExecutionError> {
+    let count = u32::from_be_bytes(read_n(bytes, pos)?) as usize;
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        values.push(decode_field(bytes, pos)?);
+    }
+    Ok(Record::new(None, values))
+}
@@ -0,0 +1,149 @@
+use dozer_core::dag::errors::ExecutionError;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, Record, Schema};
+use sqlparser::ast::{BinaryOperator, Expr, Value};
+
+/// Resolves a (possibly table-qualified) column reference against `schema`
+/// by name. A `table.column` reference first matches a field named exactly
+/// `table.column` (how a join's output schema names a column present on
+/// both sides); failing that, the qualifier is ignored and `column` is
+/// looked up on its own.
+pub fn resolve_column(schema: &Schema, expr: &Expr) -> Result<usize, ExecutionError> {
+    let name = match expr {
+        Expr::Identifier(ident) => &ident.value,
+        Expr::CompoundIdentifier(parts) => {
+            let qualified = parts
+                .iter()
+                .map(|i| i.value.as_str())
+                .collect::<Vec<_>>()
+                .join(".");
+            if let Some(idx) = schema.fields.iter().position(|f| f.name == qualified) {
+                return Ok(idx);
+            }
+            &parts
+                .last()
+                .ok_or_else(|| ExecutionError::InvalidOperation("empty column reference".into()))?
+                .value
+        }
+        other => {
+            return Err(ExecutionError::InvalidOperation(format!(
+                "expected a column reference, got `{}`",
+                other
+            )))
+        }
+    };
+    schema
+        .fields
+        .iter()
+        .position(|f| &f.name == name)
+        .ok_or_else(|| ExecutionError::InvalidOperation(format!("unknown column `{}`", name)))
+}
+
+/// Evaluates the subset of SQL expressions `WHERE`/`HAVING` predicates use in
+/// this pipeline: column references, literals, comparisons and `AND`/`OR`.
+pub fn evaluate(schema: &Schema, expr: &Expr, record: &Record) -> Result<Field, ExecutionError> {
+    match expr {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+            let idx = resolve_column(schema, expr)?;
+            Ok(record.values[idx].clone())
+        }
+        Expr::Value(Value::Number(n, _)) => match n.parse::<i64>() {
+            Ok(i) => Ok(Field::Int(i)),
+            Err(_) => n
+                .parse::<f64>()
+                .map(|f| Field::Float(OrderedFloat(f)))
+                .map_err(|_| ExecutionError::InvalidOperation(format!("invalid number `{}`", n))),
+        },
+        Expr::Value(Value::SingleQuotedString(s)) | Expr::Value(Value::DoubleQuotedString(s)) => {
+            Ok(Field::String(s.clone()))
+        }
+        Expr::Value(Value::Boolean(b)) => Ok(Field::Boolean(*b)),
+        Expr::Value(Value::Null) => Ok(Field::Null),
+        Expr::BinaryOp { left, op, right } => evaluate_binary_op(schema, op, left, right, record),
+        other => Err(ExecutionError::InvalidOperation(format!(
+            "unsupported expression `{}`",
+            other
+        ))),
+    }
+}
+
+fn evaluate_binary_op(
+    schema: &Schema,
+    op: &BinaryOperator,
+    left: &Expr,
+    right: &Expr,
+    record: &Record,
+) -> Result<Field, ExecutionError> {
+    match op {
+        BinaryOperator::And => {
+            if !as_bool(&evaluate(schema, left, record)?)? {
+                return Ok(Field::Boolean(false));
+            }
+            Ok(Field::Boolean(as_bool(&evaluate(schema, right, record)?)?))
+        }
+        BinaryOperator::Or => {
+            if as_bool(&evaluate(schema, left, record)?)? {
+                return Ok(Field::Boolean(true));
+            }
+            Ok(Field::Boolean(as_bool(&evaluate(schema, right, record)?)?))
+        }
+        _ => {
+            let l = evaluate(schema, left, record)?;
+            let r = evaluate(schema, right, record)?;
+            Ok(Field::Boolean(compare(op, &l, &r)?))
+        }
+    }
+}
+
+fn as_bool(field: &Field) -> Result<bool, ExecutionError> {
+    match field {
+        Field::Boolean(b) => Ok(*b),
+        other => Err(ExecutionError::InvalidOperation(format!(
+            "expected a boolean, got {:?}",
+            other
+        ))),
+    }
+}
+
+fn as_f64(field: &Field) -> Option<f64> {
+    match field {
+        Field::Int(v) => Some(*v as f64),
+        Field::Float(v) => Some(v.0),
+        _ => None,
+    }
+}
+
+fn compare(op: &BinaryOperator, left: &Field, right: &Field) -> Result<bool, ExecutionError> {
+    use BinaryOperator::*;
+    if let (Some(l), Some(r)) = (as_f64(left), as_f64(right)) {
+        return match op {
+            Eq => Ok(l == r),
+            NotEq => Ok(l != r),
+            Lt => Ok(l < r),
+            LtEq => Ok(l <= r),
+            Gt => Ok(l > r),
+            GtEq => Ok(l >= r),
+            _ => Err(ExecutionError::InvalidOperation(format!(
+                "unsupported operator `{}`",
+                op
+            ))),
+        };
+    }
+    match op {
+        Eq => Ok(left == right),
+        NotEq => Ok(left != right),
+        _ => Err(ExecutionError::InvalidOperation(format!(
+            "cannot compare {:?} and {:?} with `{}`",
+            left, right, op
+        ))),
+    }
+}
+
+/// Evaluates `expr` as a boolean predicate against `record`.
+pub fn evaluate_predicate(
+    schema: &Schema,
+    expr: &Expr,
+    record: &Record,
+) -> Result<bool, ExecutionError> {
+    as_bool(&evaluate(schema, expr, record)?)
+}
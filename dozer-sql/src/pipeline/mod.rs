@@ -0,0 +1,5 @@
+pub mod builder;
+pub(crate) mod encoding;
+pub mod expression;
+pub mod processor;
+pub mod product;
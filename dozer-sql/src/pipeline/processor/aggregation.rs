@@ -0,0 +1,206 @@
+use crate::pipeline::encoding::{decode_record, encode_record};
+use crate::pipeline::expression::{evaluate_predicate, resolve_column};
+use dozer_core::dag::channels::ProcessorChannelForwarder;
+use dozer_core::dag::dag::PortHandle;
+use dozer_core::dag::errors::ExecutionError;
+use dozer_core::dag::node::{OutputPortDef, OutputPortDefOptions, Processor, ProcessorFactory};
+use dozer_core::dag::record_store::RecordReader;
+use dozer_core::storage::common::{Database, Environment, RwTransaction};
+use dozer_types::types::{Operation, Record, Schema};
+use sqlparser::ast::Expr;
+use std::collections::HashMap;
+
+/// `GROUP BY` with an optional `HAVING` filter. Without aggregate functions
+/// in the `SELECT` list to fold rows into (none are parsed anywhere in this
+/// pipeline yet), grouping degenerates to emitting one representative row
+/// per distinct `group_by` key — i.e. `SELECT DISTINCT` on those columns —
+/// and retracting it once every row sharing that key has been deleted.
+pub struct AggregationProcessorFactory {
+    group_indexes: Vec<usize>,
+    having: Option<Expr>,
+    schema: Schema,
+}
+
+impl AggregationProcessorFactory {
+    pub fn new(group_by: Vec<Expr>, having: Option<Expr>, schema: Schema) -> Self {
+        let group_indexes = group_by
+            .iter()
+            .filter_map(|e| resolve_column(&schema, e).ok())
+            .collect();
+        Self {
+            group_indexes,
+            having,
+            schema,
+        }
+    }
+}
+
+impl ProcessorFactory for AggregationProcessorFactory {
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            OutputPortDefOptions::default(),
+        )]
+    }
+
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, ExecutionError> {
+        Ok(self.schema.clone())
+    }
+
+    fn build(&self) -> Box<dyn Processor> {
+        Box::new(AggregationProcessor {
+            group_indexes: self.group_indexes.clone(),
+            having: self.having.clone(),
+            schema: self.schema.clone(),
+            groups_db: None,
+        })
+    }
+}
+
+pub struct AggregationProcessor {
+    group_indexes: Vec<usize>,
+    having: Option<Expr>,
+    schema: Schema,
+    /// Group key (encoded like a record, via `encode_record`) -> row count
+    /// sharing that key (8 bytes, big-endian) followed by the encoded
+    /// representative row. Lives in the state store rather than an in-memory
+    /// map so a group's count stays in lockstep with the window's
+    /// `RwTransaction`: an aborted window leaves no trace, and a replayed one
+    /// doesn't double-count rows already folded in by a prior commit.
+    groups_db: Option<Database>,
+}
+
+impl AggregationProcessor {
+    fn group_key(&self, record: &Record) -> Vec<u8> {
+        let key_values = self
+            .group_indexes
+            .iter()
+            .map(|&idx| record.values[idx].clone())
+            .collect();
+        let mut out = Vec::new();
+        encode_record(&Record::new(None, key_values), &mut out);
+        out
+    }
+
+    fn passes_having(&self, record: &Record) -> Result<bool, ExecutionError> {
+        match &self.having {
+            Some(having) => evaluate_predicate(&self.schema, having, record),
+            None => Ok(true),
+        }
+    }
+
+    fn db(&self) -> Result<&Database, ExecutionError> {
+        self.groups_db
+            .as_ref()
+            .ok_or_else(|| ExecutionError::InvalidOperation("aggregation processor not initialised".into()))
+    }
+
+    fn load_group(
+        &self,
+        state: &mut dyn RwTransaction,
+        key: &[u8],
+    ) -> Result<Option<(u64, Record)>, ExecutionError> {
+        let Some(bytes) = state.get(self.db()?, key)? else {
+            return Ok(None);
+        };
+        let mut pos = 0;
+        let count = u64::from_be_bytes(
+            bytes
+                .get(..8)
+                .ok_or_else(|| ExecutionError::InternalStringError("truncated group entry".into()))?
+                .try_into()
+                .unwrap(),
+        );
+        pos += 8;
+        let representative = decode_record(&bytes, &mut pos)?;
+        Ok(Some((count, representative)))
+    }
+
+    fn store_group(
+        &self,
+        state: &mut dyn RwTransaction,
+        key: &[u8],
+        count: u64,
+        representative: &Record,
+    ) -> Result<(), ExecutionError> {
+        let mut bytes = count.to_be_bytes().to_vec();
+        encode_record(representative, &mut bytes);
+        state.put(self.db()?, key, &bytes)
+    }
+
+    fn insert(
+        &mut self,
+        record: Record,
+        state: &mut dyn RwTransaction,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let key = self.group_key(&record);
+        let (count, representative) = match self.load_group(state, &key)? {
+            Some((count, representative)) => (count + 1, representative),
+            None => (1, record.clone()),
+        };
+        self.store_group(state, &key, count, &representative)?;
+        if count == 1 && self.passes_having(&record)? {
+            fw.send(
+                Operation::Insert { new: record },
+                dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn delete(
+        &mut self,
+        record: Record,
+        state: &mut dyn RwTransaction,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let key = self.group_key(&record);
+        let Some((count, representative)) = self.load_group(state, &key)? else {
+            return Ok(());
+        };
+        if count <= 1 {
+            state.del(self.db()?, &key)?;
+            if self.passes_having(&representative)? {
+                fw.send(
+                    Operation::Delete {
+                        old: representative,
+                    },
+                    dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+                )?;
+            }
+        } else {
+            self.store_group(state, &key, count - 1, &representative)?;
+        }
+        Ok(())
+    }
+}
+
+impl Processor for AggregationProcessor {
+    fn init(&mut self, env: &mut dyn Environment) -> Result<(), ExecutionError> {
+        self.groups_db = Some(env.open_database("aggregation_groups")?);
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+        state: &mut dyn RwTransaction,
+        _reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError> {
+        match op {
+            Operation::Insert { new } => self.insert(new, state, fw),
+            Operation::Delete { old } => self.delete(old, state, fw),
+            Operation::Update { old, new } => {
+                self.delete(old, state, fw)?;
+                self.insert(new, state, fw)
+            }
+        }
+    }
+}
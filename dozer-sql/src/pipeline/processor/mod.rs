@@ -0,0 +1,3 @@
+pub mod aggregation;
+pub mod projection;
+pub mod selection;
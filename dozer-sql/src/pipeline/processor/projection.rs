@@ -0,0 +1,132 @@
+use crate::pipeline::expression::resolve_column;
+use dozer_core::dag::channels::ProcessorChannelForwarder;
+use dozer_core::dag::dag::PortHandle;
+use dozer_core::dag::errors::ExecutionError;
+use dozer_core::dag::node::{OutputPortDef, OutputPortDefOptions, Processor, ProcessorFactory};
+use dozer_core::dag::record_store::RecordReader;
+use dozer_core::storage::common::{Environment, RwTransaction};
+use dozer_types::types::{Operation, Record, Schema};
+use sqlparser::ast::{Expr, SelectItem};
+use std::collections::HashMap;
+
+/// Emits the `SELECT` list's columns, in order, dropping everything else
+/// from the input schema/record. Column references are resolved to field
+/// indexes against `input_schema` once, at build time.
+pub struct ProjectionProcessorFactory {
+    indexes: Vec<usize>,
+    output_schema: Schema,
+}
+
+impl ProjectionProcessorFactory {
+    pub fn new(
+        select_items: Vec<SelectItem>,
+        input_schema: Schema,
+    ) -> Result<Self, ExecutionError> {
+        let mut indexes = Vec::new();
+        let mut output_schema = Schema::empty();
+
+        for item in &select_items {
+            match item {
+                SelectItem::Wildcard | SelectItem::QualifiedWildcard(_) => {
+                    for (idx, field) in input_schema.fields.iter().enumerate() {
+                        indexes.push(idx);
+                        output_schema.field(field.clone(), false, false);
+                    }
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    let idx = resolve_column(&input_schema, expr)?;
+                    indexes.push(idx);
+                    output_schema.field(input_schema.fields[idx].clone(), false, false);
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    let idx = resolve_projected_expr(&input_schema, expr)?;
+                    indexes.push(idx);
+                    let mut field = input_schema.fields[idx].clone();
+                    field.name = alias.value.clone();
+                    output_schema.field(field, false, false);
+                }
+            }
+        }
+
+        Ok(Self {
+            indexes,
+            output_schema,
+        })
+    }
+
+    pub fn output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
+
+fn resolve_projected_expr(schema: &Schema, expr: &Expr) -> Result<usize, ExecutionError> {
+    resolve_column(schema, expr)
+}
+
+impl ProcessorFactory for ProjectionProcessorFactory {
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            OutputPortDefOptions::default(),
+        )]
+    }
+
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, ExecutionError> {
+        Ok(self.output_schema.clone())
+    }
+
+    fn build(&self) -> Box<dyn Processor> {
+        Box::new(ProjectionProcessor {
+            indexes: self.indexes.clone(),
+        })
+    }
+}
+
+pub struct ProjectionProcessor {
+    indexes: Vec<usize>,
+}
+
+impl ProjectionProcessor {
+    fn project(&self, record: &Record) -> Record {
+        Record::new(
+            None,
+            self.indexes
+                .iter()
+                .map(|&idx| record.values[idx].clone())
+                .collect(),
+        )
+    }
+}
+
+impl Processor for ProjectionProcessor {
+    fn init(&mut self, _env: &mut dyn Environment) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+        _state: &mut dyn RwTransaction,
+        _reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError> {
+        let projected = match op {
+            Operation::Insert { new } => Operation::Insert {
+                new: self.project(&new),
+            },
+            Operation::Update { old, new } => Operation::Update {
+                old: self.project(&old),
+                new: self.project(&new),
+            },
+            Operation::Delete { old } => Operation::Delete {
+                old: self.project(&old),
+            },
+        };
+        fw.send(projected, dozer_core::dag::dag::DEFAULT_PORT_HANDLE)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::pipeline::expression::evaluate_predicate;
+use dozer_core::dag::channels::ProcessorChannelForwarder;
+use dozer_core::dag::dag::PortHandle;
+use dozer_core::dag::errors::ExecutionError;
+use dozer_core::dag::node::{OutputPortDef, OutputPortDefOptions, Processor, ProcessorFactory};
+use dozer_core::dag::record_store::RecordReader;
+use dozer_core::storage::common::{Environment, RwTransaction};
+use dozer_types::types::{Operation, Schema};
+use sqlparser::ast::Expr;
+use std::collections::HashMap;
+
+/// Passes through operations whose record satisfies the `WHERE` predicate
+/// and drops the rest. `schema` is the upstream schema the predicate's
+/// column references are resolved against.
+pub struct SelectionProcessorFactory {
+    predicate: Expr,
+    schema: Schema,
+}
+
+impl SelectionProcessorFactory {
+    pub fn new(predicate: Expr, schema: Schema) -> Self {
+        Self { predicate, schema }
+    }
+}
+
+impl ProcessorFactory for SelectionProcessorFactory {
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            OutputPortDefOptions::default(),
+        )]
+    }
+
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, ExecutionError> {
+        input_schemas
+            .values()
+            .next()
+            .cloned()
+            .ok_or_else(|| ExecutionError::InvalidOperation("missing input schema".into()))
+    }
+
+    fn build(&self) -> Box<dyn Processor> {
+        Box::new(SelectionProcessor {
+            predicate: self.predicate.clone(),
+            schema: self.schema.clone(),
+        })
+    }
+}
+
+pub struct SelectionProcessor {
+    predicate: Expr,
+    schema: Schema,
+}
+
+impl Processor for SelectionProcessor {
+    fn init(&mut self, _env: &mut dyn Environment) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+        _state: &mut dyn RwTransaction,
+        _reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError> {
+        let matches = match &op {
+            Operation::Insert { new } => evaluate_predicate(&self.schema, &self.predicate, new)?,
+            Operation::Update { new, .. } => {
+                evaluate_predicate(&self.schema, &self.predicate, new)?
+            }
+            Operation::Delete { old } => evaluate_predicate(&self.schema, &self.predicate, old)?,
+        };
+        if matches {
+            fw.send(op, dozer_core::dag::dag::DEFAULT_PORT_HANDLE)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+use crate::pipeline::encoding::{decode_record, encode_record};
+use dozer_core::dag::channels::ProcessorChannelForwarder;
+use dozer_core::dag::dag::{NodeHandle, PortHandle};
+use dozer_core::dag::errors::ExecutionError;
+use dozer_core::dag::node::{OutputPortDef, OutputPortDefOptions, Processor, ProcessorFactory};
+use dozer_core::dag::record_store::RecordReader;
+use dozer_core::storage::common::{Database, Environment, RwTransaction};
+use dozer_types::types::{Field, FieldDefinition, Operation, Record, Schema};
+use std::collections::HashMap;
+
+pub const LEFT_JOIN_PORT: PortHandle = 0;
+pub const RIGHT_JOIN_PORT: PortHandle = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left,
+}
+
+/// Incremental hash-join over two input ports. Each side's rows are indexed
+/// in the state store keyed by its join column; an incoming op from one side
+/// probes the other side's index for matches and emits the concatenated
+/// joined records (or retractions, for `Update`/`Delete`, so downstream
+/// aggregations stay correct). `Left` also emits a null-padded row the
+/// moment a left-side insert finds no match yet on the right.
+pub struct JoinProcessorFactory {
+    left_source: NodeHandle,
+    right_source: NodeHandle,
+    left_join_key: usize,
+    right_join_key: usize,
+    join_type: JoinType,
+    /// Width of the right side's schema, needed to null-pad an unmatched
+    /// `Left` row to the declared output schema.
+    right_field_count: usize,
+}
+
+impl JoinProcessorFactory {
+    pub fn new(
+        left_source: NodeHandle,
+        right_source: NodeHandle,
+        left_join_key: usize,
+        right_join_key: usize,
+        join_type: JoinType,
+        right_field_count: usize,
+    ) -> Self {
+        Self {
+            left_source,
+            right_source,
+            left_join_key,
+            right_join_key,
+            join_type,
+            right_field_count,
+        }
+    }
+}
+
+impl ProcessorFactory for JoinProcessorFactory {
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            OutputPortDefOptions::default(),
+        )]
+    }
+
+    /// Concatenates the left schema's fields followed by the right schema's,
+    /// qualifying a field name with its source table (`NodeHandle::id`) when
+    /// the same name appears on both sides.
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, ExecutionError> {
+        let left = input_schemas
+            .get(&LEFT_JOIN_PORT)
+            .ok_or_else(|| ExecutionError::InvalidOperation("missing left schema".into()))?;
+        let right = input_schemas
+            .get(&RIGHT_JOIN_PORT)
+            .ok_or_else(|| ExecutionError::InvalidOperation("missing right schema".into()))?;
+
+        let mut out = Schema::empty();
+        for field in &left.fields {
+            out.field(qualify(field, &self.left_source, right), false, false);
+        }
+        for field in &right.fields {
+            out.field(qualify(field, &self.right_source, left), false, false);
+        }
+        Ok(out)
+    }
+
+    fn build(&self) -> Box<dyn Processor> {
+        Box::new(JoinProcessor {
+            left_join_key: self.left_join_key,
+            right_join_key: self.right_join_key,
+            join_type: self.join_type,
+            right_field_count: self.right_field_count,
+            left_index_db: None,
+            right_index_db: None,
+        })
+    }
+}
+
+fn qualify(field: &FieldDefinition, owner: &NodeHandle, other: &Schema) -> FieldDefinition {
+    let name = if other.fields.iter().any(|f| f.name == field.name) {
+        format!("{}.{}", owner.id, field.name)
+    } else {
+        field.name.clone()
+    };
+    FieldDefinition::new(name, field.typ, field.nullable)
+}
+
+struct JoinProcessor {
+    left_join_key: usize,
+    right_join_key: usize,
+    join_type: JoinType,
+    right_field_count: usize,
+    left_index_db: Option<Database>,
+    right_index_db: Option<Database>,
+}
+
+/// Decodes every record appended to a bucket by `index_insert`.
+fn decode_bucket(bytes: &[u8]) -> Result<Vec<Record>, ExecutionError> {
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        records.push(decode_record(bytes, &mut pos)?);
+    }
+    Ok(records)
+}
+
+fn encode_bucket(records: &[Record]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for record in records {
+        encode_record(record, &mut out);
+    }
+    out
+}
+
+/// Concatenates `left`'s fields followed by `right`'s into one joined
+/// record, matching the schema `JoinProcessorFactory::get_output_schema`
+/// declares.
+fn concat(left: &Record, right: &Record) -> Record {
+    let mut values = left.values.clone();
+    values.extend(right.values.iter().cloned());
+    Record::new(None, values)
+}
+
+fn null_row(field_count: usize) -> Record {
+    Record::new(None, vec![Field::Null; field_count])
+}
+
+impl JoinProcessor {
+    fn key_bytes(record: &Record, join_key: usize) -> Vec<u8> {
+        match &record.values[join_key] {
+            Field::Int(v) => v.to_be_bytes().to_vec(),
+            Field::String(v) => v.clone().into_bytes(),
+            other => format!("{:?}", other).into_bytes(),
+        }
+    }
+
+    /// Appends `record` to the bucket already stored for `key`, so a
+    /// repeated join key accumulates every matching row rather than
+    /// overwriting the previous one.
+    fn index_insert(
+        tx: &mut dyn RwTransaction,
+        db: &Database,
+        key: &[u8],
+        record: &Record,
+    ) -> Result<(), ExecutionError> {
+        let mut bucket = tx.get(db, key)?.unwrap_or_default();
+        encode_record(record, &mut bucket);
+        tx.put(db, key, &bucket)
+    }
+
+    /// Removes the first record equal to `record` from `key`'s bucket, for
+    /// `Update`/`Delete` retractions. Deletes `key` altogether once its
+    /// bucket has no records left.
+    fn index_remove(
+        tx: &mut dyn RwTransaction,
+        db: &Database,
+        key: &[u8],
+        record: &Record,
+    ) -> Result<(), ExecutionError> {
+        let Some(bucket) = tx.get(db, key)? else {
+            return Ok(());
+        };
+        let mut records = decode_bucket(&bucket)?;
+        if let Some(pos) = records.iter().position(|r| r.values == record.values) {
+            records.remove(pos);
+        }
+        if records.is_empty() {
+            tx.del(db, key)?;
+        } else {
+            tx.put(db, key, &encode_bucket(&records))?;
+        }
+        Ok(())
+    }
+
+    fn index_lookup(
+        tx: &mut dyn RwTransaction,
+        db: &Database,
+        key: &[u8],
+    ) -> Result<Vec<Record>, ExecutionError> {
+        match tx.get(db, key)? {
+            Some(bucket) => decode_bucket(&bucket),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Handles one side's insert: indexes `new`, probes the other side for
+    /// matches and emits the joined rows (or, for `Left` with no match yet
+    /// on the right, a null-padded row).
+    #[allow(clippy::too_many_arguments)]
+    fn handle_insert(
+        &self,
+        new: &Record,
+        own_key: usize,
+        own_db: &Database,
+        other_db: &Database,
+        other_field_count: usize,
+        is_left: bool,
+        state: &mut dyn RwTransaction,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let key = Self::key_bytes(new, own_key);
+        Self::index_insert(state, own_db, &key, new)?;
+        let matches = Self::index_lookup(state, other_db, &key)?;
+
+        if matches.is_empty() {
+            if is_left && self.join_type == JoinType::Left {
+                fw.send(
+                    Operation::Insert {
+                        new: concat(new, &null_row(other_field_count)),
+                    },
+                    dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+                )?;
+            }
+            return Ok(());
+        }
+
+        for other in &matches {
+            let joined = if is_left {
+                concat(new, other)
+            } else {
+                concat(other, new)
+            };
+            fw.send(
+                Operation::Insert { new: joined },
+                dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Handles one side's delete: un-indexes `old` and retracts whatever it
+    /// had previously joined to (or the null-padded row, for an unmatched
+    /// `Left` row).
+    #[allow(clippy::too_many_arguments)]
+    fn handle_delete(
+        &self,
+        old: &Record,
+        own_key: usize,
+        own_db: &Database,
+        other_db: &Database,
+        other_field_count: usize,
+        is_left: bool,
+        state: &mut dyn RwTransaction,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let key = Self::key_bytes(old, own_key);
+        let matches = Self::index_lookup(state, other_db, &key)?;
+        Self::index_remove(state, own_db, &key, old)?;
+
+        if matches.is_empty() {
+            if is_left && self.join_type == JoinType::Left {
+                fw.send(
+                    Operation::Delete {
+                        old: concat(old, &null_row(other_field_count)),
+                    },
+                    dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+                )?;
+            }
+            return Ok(());
+        }
+
+        for other in &matches {
+            let joined = if is_left {
+                concat(old, other)
+            } else {
+                concat(other, old)
+            };
+            fw.send(
+                Operation::Delete { old: joined },
+                dozer_core::dag::dag::DEFAULT_PORT_HANDLE,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Processor for JoinProcessor {
+    fn init(&mut self, env: &mut dyn Environment) -> Result<(), ExecutionError> {
+        self.left_index_db = Some(env.open_database("join_left_index")?);
+        self.right_index_db = Some(env.open_database("join_right_index")?);
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+        state: &mut dyn RwTransaction,
+        _reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError> {
+        let left_db = self
+            .left_index_db
+            .clone()
+            .ok_or_else(|| ExecutionError::InvalidOperation("join processor not initialised".into()))?;
+        let right_db = self
+            .right_index_db
+            .clone()
+            .ok_or_else(|| ExecutionError::InvalidOperation("join processor not initialised".into()))?;
+
+        match from_port {
+            LEFT_JOIN_PORT => match op {
+                Operation::Insert { new } => self.handle_insert(
+                    &new,
+                    self.left_join_key,
+                    &left_db,
+                    &right_db,
+                    self.right_field_count,
+                    true,
+                    state,
+                    fw,
+                ),
+                Operation::Delete { old } => self.handle_delete(
+                    &old,
+                    self.left_join_key,
+                    &left_db,
+                    &right_db,
+                    self.right_field_count,
+                    true,
+                    state,
+                    fw,
+                ),
+                Operation::Update { old, new } => {
+                    self.handle_delete(
+                        &old,
+                        self.left_join_key,
+                        &left_db,
+                        &right_db,
+                        self.right_field_count,
+                        true,
+                        state,
+                        fw,
+                    )?;
+                    self.handle_insert(
+                        &new,
+                        self.left_join_key,
+                        &left_db,
+                        &right_db,
+                        self.right_field_count,
+                        true,
+                        state,
+                        fw,
+                    )
+                }
+            },
+            RIGHT_JOIN_PORT => match op {
+                Operation::Insert { new } => self.handle_insert(
+                    &new,
+                    self.right_join_key,
+                    &right_db,
+                    &left_db,
+                    0,
+                    false,
+                    state,
+                    fw,
+                ),
+                Operation::Delete { old } => self.handle_delete(
+                    &old,
+                    self.right_join_key,
+                    &right_db,
+                    &left_db,
+                    0,
+                    false,
+                    state,
+                    fw,
+                ),
+                Operation::Update { old, new } => {
+                    self.handle_delete(
+                        &old,
+                        self.right_join_key,
+                        &right_db,
+                        &left_db,
+                        0,
+                        false,
+                        state,
+                        fw,
+                    )?;
+                    self.handle_insert(
+                        &new,
+                        self.right_join_key,
+                        &right_db,
+                        &left_db,
+                        0,
+                        false,
+                        state,
+                        fw,
+                    )
+                }
+            },
+            other => Err(ExecutionError::InvalidPortHandle(other)),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod join;
+
+#[cfg(test)]
+mod tests;
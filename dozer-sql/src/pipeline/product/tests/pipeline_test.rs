@@ -1,11 +1,10 @@
 use crate::pipeline::builder::PipelineBuilder;
 use dozer_core::dag::channels::SourceChannelForwarder;
-use dozer_core::dag::dag::{Endpoint, NodeType, DEFAULT_PORT_HANDLE};
+use dozer_core::dag::dag::{Endpoint, NodeHandle, NodeType, PortHandle, DEFAULT_PORT_HANDLE};
 use dozer_core::dag::errors::ExecutionError;
 use dozer_core::dag::executor::{DagExecutor, ExecutorOptions};
 use dozer_core::dag::node::{
-    NodeHandle, OutputPortDef, OutputPortDefOptions, PortHandle, Sink, SinkFactory, Source,
-    SourceFactory,
+    OutputPortDef, OutputPortDefOptions, Sink, SinkFactory, Source, SourceFactory,
 };
 use dozer_core::dag::record_store::RecordReader;
 use dozer_core::storage::common::{Environment, RwTransaction};
@@ -17,7 +16,7 @@ use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
 use std::collections::HashMap;
 use std::fs;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tempdir::TempDir;
 
 /// Test Source
@@ -80,33 +79,41 @@ impl Source for UserTestSource {
         fw: &mut dyn SourceChannelForwarder,
         _from_seq: Option<u64>,
     ) -> Result<(), ExecutionError> {
-        for n in 0..10000 {
-            fw.send(
-                n,
-                Operation::Insert {
-                    new: Record::new(
-                        None,
-                        vec![
-                            Field::Int(0),
-                            Field::String("Alice".to_string()),
-                            Field::Int(0),
-                            Field::Float(OrderedFloat(5.5)),
-                        ],
-                    ),
-                },
-                DEFAULT_PORT_HANDLE,
-            )
-            .unwrap();
-        }
-        Ok(())
+        let ops = (0..10000)
+            .map(|n| {
+                (
+                    n,
+                    Operation::Insert {
+                        new: Record::new(
+                            None,
+                            vec![
+                                Field::Int(0),
+                                Field::String("Alice".to_string()),
+                                Field::Int(0),
+                                Field::Float(OrderedFloat(5.5)),
+                            ],
+                        ),
+                    },
+                )
+            })
+            .collect();
+        fw.send_batch(ops, DEFAULT_PORT_HANDLE)
     }
 }
 
-/// Test Source
+/// The join's right-hand test source: a single department row whose `id`
+/// matches every `UserTestSource` row's `DepartmentID`, so a join against it
+/// exercises both sides of `JoinProcessor`'s keyed index.
 pub struct DepartmentTestSourceFactory {
     output_ports: Vec<PortHandle>,
 }
 
+impl DepartmentTestSourceFactory {
+    pub fn new(output_ports: Vec<PortHandle>) -> Self {
+        Self { output_ports }
+    }
+}
+
 impl SourceFactory for DepartmentTestSourceFactory {
     fn get_output_ports(&self) -> Vec<OutputPortDef> {
         self.output_ports
@@ -115,13 +122,6 @@ impl SourceFactory for DepartmentTestSourceFactory {
             .collect()
     }
 
-    fn build(
-        &self,
-        _output_schemas: HashMap<PortHandle, Schema>,
-    ) -> Result<Box<dyn Source>, ExecutionError> {
-        Ok(Box::new(DepartmentTestSource {}))
-    }
-
     fn get_output_schema(&self, _port: &PortHandle) -> Result<Schema, ExecutionError> {
         Ok(Schema::empty()
             .field(
@@ -130,12 +130,19 @@ impl SourceFactory for DepartmentTestSourceFactory {
                 false,
             )
             .field(
-                FieldDefinition::new(String::from("name"), FieldType::String, false),
+                FieldDefinition::new(String::from("dept_name"), FieldType::String, false),
                 false,
                 false,
             )
             .clone())
     }
+
+    fn build(
+        &self,
+        _output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Source>, ExecutionError> {
+        Ok(Box::new(DepartmentTestSource {}))
+    }
 }
 
 pub struct DepartmentTestSource {}
@@ -146,18 +153,81 @@ impl Source for DepartmentTestSource {
         fw: &mut dyn SourceChannelForwarder,
         _from_seq: Option<u64>,
     ) -> Result<(), ExecutionError> {
-        for n in 0..10000 {
-            fw.send(
-                n,
-                Operation::Insert {
-                    new: Record::new(None, vec![Field::Int(0), Field::String("IT".to_string())]),
-                },
-                DEFAULT_PORT_HANDLE,
-            )
-            .unwrap();
+        fw.send(
+            0,
+            Operation::Insert {
+                new: Record::new(None, vec![Field::Int(0), Field::String("Engineering".to_string())]),
+            },
+            DEFAULT_PORT_HANDLE,
+        )
+    }
+}
+
+/// A sink that keeps every inserted record it receives, so a test can assert
+/// on what actually came out the other end of the pipeline instead of just
+/// that the executor didn't error.
+pub struct CapturingSinkFactory {
+    input_ports: Vec<PortHandle>,
+    received: Arc<Mutex<Vec<Record>>>,
+}
+
+impl CapturingSinkFactory {
+    pub fn new(input_ports: Vec<PortHandle>, received: Arc<Mutex<Vec<Record>>>) -> Self {
+        Self {
+            input_ports,
+            received,
+        }
+    }
+}
+
+impl SinkFactory for CapturingSinkFactory {
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        self.input_ports.clone()
+    }
+
+    fn set_input_schema(
+        &self,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Sink>, ExecutionError> {
+        Ok(Box::new(CapturingSink {
+            received: self.received.clone(),
+        }))
+    }
+}
+
+pub struct CapturingSink {
+    received: Arc<Mutex<Vec<Record>>>,
+}
+
+impl Sink for CapturingSink {
+    fn init(&mut self, _env: &mut dyn Environment) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        _seq: u64,
+        op: Operation,
+        _state: &mut dyn RwTransaction,
+        _reader: &HashMap<PortHandle, RecordReader>,
+    ) -> Result<(), ExecutionError> {
+        if let Operation::Insert { new } = op {
+            self.received.lock().unwrap().push(new);
         }
         Ok(())
     }
+
+    fn commit(&self, _tx: &mut dyn RwTransaction) -> Result<(), ExecutionError> {
+        Ok(())
+    }
 }
 
 pub struct TestSinkFactory {
@@ -224,12 +294,19 @@ fn test_single_table_pipeline() {
 
     let statement: &Statement = &ast[0];
 
-    let builder = PipelineBuilder::new(Some(1));
-    let (mut dag, mut in_handle, out_handle) =
-        builder.statement_to_pipeline(statement.clone()).unwrap();
-
     let user_source = UserTestSourceFactory::new(vec![DEFAULT_PORT_HANDLE]);
 
+    let mut table_schemas = HashMap::new();
+    table_schemas.insert(
+        "Users".to_string(),
+        user_source.get_output_schema(&DEFAULT_PORT_HANDLE).unwrap(),
+    );
+
+    let builder = PipelineBuilder::new(Some(1));
+    let (mut dag, mut in_handle, out_handle) = builder
+        .statement_to_pipeline(statement.clone(), &table_schemas)
+        .unwrap();
+
     let sink = TestSinkFactory::new(vec![DEFAULT_PORT_HANDLE]);
 
     dag.add_node(
@@ -242,9 +319,9 @@ fn test_single_table_pipeline() {
         NodeHandle::new(Some(1), String::from("sink")),
     );
 
-    let input_point = in_handle.remove("users").unwrap();
+    let input_point = in_handle.remove("Users").unwrap();
 
-    let _source_to_users = dag.connect(
+    dag.connect(
         Endpoint::new(
             NodeHandle::new(Some(1), String::from("users")),
             DEFAULT_PORT_HANDLE,
@@ -252,7 +329,7 @@ fn test_single_table_pipeline() {
         Endpoint::new(input_point.node, input_point.port),
     );
 
-    let _output_to_sink = dag.connect(
+    dag.connect(
         Endpoint::new(out_handle.node, out_handle.port),
         Endpoint::new(
             NodeHandle::new(Some(1), String::from("sink")),
@@ -279,4 +356,91 @@ fn test_single_table_pipeline() {
 
     let elapsed = now.elapsed();
     debug!("Elapsed: {:.2?}", elapsed);
+}
+
+#[test]
+fn test_join_pipeline() {
+    let sql =
+        "SELECT name, dept_name FROM Users JOIN Departments ON Users.DepartmentID = Departments.id";
+
+    let dialect = GenericDialect {};
+    let ast = Parser::parse_sql(&dialect, sql).unwrap();
+    let statement: &Statement = &ast[0];
+
+    let user_source = UserTestSourceFactory::new(vec![DEFAULT_PORT_HANDLE]);
+    let department_source = DepartmentTestSourceFactory::new(vec![DEFAULT_PORT_HANDLE]);
+
+    let mut table_schemas = HashMap::new();
+    table_schemas.insert(
+        "Users".to_string(),
+        user_source.get_output_schema(&DEFAULT_PORT_HANDLE).unwrap(),
+    );
+    table_schemas.insert(
+        "Departments".to_string(),
+        department_source
+            .get_output_schema(&DEFAULT_PORT_HANDLE)
+            .unwrap(),
+    );
+
+    let builder = PipelineBuilder::new(Some(2));
+    let (mut dag, mut in_handle, out_handle) = builder
+        .statement_to_pipeline(statement.clone(), &table_schemas)
+        .unwrap();
+
+    let received: Arc<Mutex<Vec<Record>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = CapturingSinkFactory::new(vec![DEFAULT_PORT_HANDLE], received.clone());
+
+    dag.add_node(
+        NodeType::Source(Arc::new(user_source)),
+        NodeHandle::new(Some(2), String::from("users")),
+    );
+    dag.add_node(
+        NodeType::Source(Arc::new(department_source)),
+        NodeHandle::new(Some(2), String::from("departments")),
+    );
+    dag.add_node(
+        NodeType::Sink(Arc::new(sink)),
+        NodeHandle::new(Some(2), String::from("sink")),
+    );
+
+    let users_point = in_handle.remove("Users").unwrap();
+    dag.connect(
+        Endpoint::new(
+            NodeHandle::new(Some(2), String::from("users")),
+            DEFAULT_PORT_HANDLE,
+        ),
+        Endpoint::new(users_point.node, users_point.port),
+    );
+
+    let departments_point = in_handle.remove("Departments").unwrap();
+    dag.connect(
+        Endpoint::new(
+            NodeHandle::new(Some(2), String::from("departments")),
+            DEFAULT_PORT_HANDLE,
+        ),
+        Endpoint::new(departments_point.node, departments_point.port),
+    );
+
+    dag.connect(
+        Endpoint::new(out_handle.node, out_handle.port),
+        Endpoint::new(
+            NodeHandle::new(Some(2), String::from("sink")),
+            DEFAULT_PORT_HANDLE,
+        ),
+    );
+
+    let tmp_dir = TempDir::new("join_test").unwrap();
+    let mut executor = DagExecutor::new(&dag, tmp_dir.path(), ExecutorOptions::default()).unwrap();
+
+    executor
+        .start()
+        .unwrap_or_else(|e| panic!("Unable to start the Executor: {}", e));
+    assert!(executor.join().is_ok());
+
+    let rows = received.lock().unwrap();
+    assert_eq!(rows.len(), 10000);
+    for row in rows.iter() {
+        assert_eq!(row.values[0], Field::String("Alice".to_string()));
+        assert_eq!(row.values[1], Field::String("Engineering".to_string()));
+    }
 }
\ No newline at end of file
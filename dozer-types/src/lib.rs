@@ -0,0 +1,2 @@
+pub mod ordered_float;
+pub mod types;
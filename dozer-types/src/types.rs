@@ -0,0 +1,99 @@
+use crate::ordered_float::OrderedFloat;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Int,
+    Float,
+    Boolean,
+    String,
+    Binary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Field {
+    Int(i64),
+    Float(OrderedFloat),
+    Boolean(bool),
+    String(String),
+    Binary(Vec<u8>),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub typ: FieldType,
+    pub nullable: bool,
+}
+
+impl FieldDefinition {
+    pub fn new(name: String, typ: FieldType, nullable: bool) -> Self {
+        Self {
+            name,
+            typ,
+            nullable,
+        }
+    }
+}
+
+/// The schema flowing out of a node's output port. Built incrementally with
+/// `field` so processors can derive a new schema from one or more inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    pub fields: Vec<FieldDefinition>,
+    pub primary_index: Vec<usize>,
+    pub secondary_indexes: Vec<usize>,
+    pub identifier: Option<u64>,
+}
+
+impl Schema {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn field(&mut self, def: FieldDefinition, is_primary: bool, is_secondary: bool) -> &mut Self {
+        let idx = self.fields.len();
+        if is_primary {
+            self.primary_index.push(idx);
+        }
+        if is_secondary {
+            self.secondary_indexes.push(idx);
+        }
+        self.fields.push(def);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub schema_id: Option<u64>,
+    pub values: Vec<Field>,
+}
+
+impl Record {
+    pub fn new(schema_id: Option<u64>, values: Vec<Field>) -> Self {
+        Self { schema_id, values }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    Insert { new: Record },
+    Update { old: Record, new: Record },
+    Delete { old: Record },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationEvent {
+    pub seq_no: u64,
+    pub operation: Operation,
+}
+
+impl OperationEvent {
+    pub fn new(seq_no: u64, operation: Operation) -> Self {
+        Self { seq_no, operation }
+    }
+}
+
+pub type SchemaMap = HashMap<u64, Schema>;